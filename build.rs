@@ -0,0 +1,89 @@
+/// Generates the solution registry of the main binary.
+/// Every solution in `src/bin` is included as a module, so `cargo all` and `cargo time` can call it directly.
+/// Also records the toolchain, profile and features of the build, which are stored alongside benchmarks.
+use std::{env, fs, path::Path, process::Command};
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    let mut puzzles: Vec<String> = fs::read_dir(&bin_dir)
+        .unwrap()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "rs" {
+                return None;
+            }
+            // solution files are named `<year>_<day>.rs`, e.g. `2020_01.rs`.
+            let stem = path.file_stem()?.to_str()?;
+            let (year, day) = stem.split_once('_')?;
+            let is_puzzle = year.len() == 4
+                && year.parse::<u16>().is_ok()
+                && day.len() == 2
+                && day.parse::<u8>().is_ok_and(|day| (1..=25).contains(&day));
+            is_puzzle.then(|| stem.to_string())
+        })
+        .collect();
+
+    puzzles.sort_unstable();
+
+    // solution modules are left out of test builds (their tests already run with the solution binaries)
+    // and of dhat builds (every solution declares its own global allocator).
+    let cfg = r#"#[cfg(not(any(test, feature = "dhat-heap")))]"#;
+
+    let mut out = String::new();
+
+    for puzzle in &puzzles {
+        let path = bin_dir.join(format!("{puzzle}.rs"));
+        out.push_str(&format!(
+            "{cfg}\n#[allow(dead_code, unused_imports)]\n#[path = {:?}]\nmod day_{puzzle};\n\n",
+            path.display().to_string()
+        ));
+    }
+
+    let entries: Vec<String> = puzzles
+        .iter()
+        .map(|puzzle| format!("day_{puzzle}::SOLUTION"))
+        .collect();
+
+    out.push_str(&format!(
+        "{cfg}\npub const SOLUTIONS: &[Solution] = &[{}];\n\n",
+        entries.join(", ")
+    ));
+    out.push_str(
+        "#[cfg(any(test, feature = \"dhat-heap\"))]\npub const SOLUTIONS: &[Solution] = &[];\n",
+    );
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("solutions.rs"), out).unwrap();
+
+    record_build_environment();
+}
+
+fn record_build_environment() {
+    let rustc = env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+    let rustc_version = Command::new(rustc)
+        .arg("--version")
+        .output()
+        .ok()
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_default();
+
+    // e.g. `CARGO_FEATURE_DHAT_HEAP` for the `dhat-heap` feature.
+    let mut features: Vec<String> = env::vars()
+        .filter_map(|(key, _)| {
+            let feature = key.strip_prefix("CARGO_FEATURE_")?;
+            Some(feature.to_lowercase().replace('_', "-"))
+        })
+        .collect();
+    features.sort_unstable();
+
+    println!("cargo:rustc-env=AOC_BUILD_RUSTC={rustc_version}");
+    println!(
+        "cargo:rustc-env=AOC_BUILD_PROFILE={}",
+        env::var("PROFILE").unwrap_or_default()
+    );
+    println!("cargo:rustc-env=AOC_BUILD_FEATURES={}", features.join(","));
+}
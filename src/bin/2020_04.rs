@@ -90,13 +90,11 @@ impl<'a> Entry<'a> {
                 let parts: Vec<&str> = line[index + 4..].split(' ').collect();
                 if let Some(height_str) = parts.first() {
                     let height_str = height_str.trim();
-                    if height_str.ends_with("cm") {
-                        let height = &height_str[..height_str.len() - 2];
+                    if let Some(height) = height_str.strip_suffix("cm") {
                         if let Ok(height) = height.parse::<u32>() {
                             return (150..=193).contains(&height);
                         }
-                    } else if height_str.ends_with("in") {
-                        let height = &height_str[..height_str.len() - 2];
+                    } else if let Some(height) = height_str.strip_suffix("in") {
                         if let Ok(height) = height.parse::<u32>() {
                             return (59..=76).contains(&height);
                         }
@@ -188,13 +186,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(4));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(4));
     }
 }
@@ -1,6 +1,6 @@
 use hashbrown::HashSet;
 
-advent_of_code::solution!(7);
+advent_of_code::solution!(7, parse = parse);
 
 pub struct BagRule {
     color: String,
@@ -37,9 +37,11 @@ impl BagRule {
     }
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
-    let bag_rules = input.lines().map(BagRule::parse).collect::<Vec<BagRule>>();
+pub fn parse(input: &str) -> Vec<BagRule> {
+    input.lines().map(BagRule::parse).collect()
+}
 
+pub fn part_one(bag_rules: &[BagRule]) -> Option<usize> {
     let mut queue = vec!["shiny gold"];
     let mut visited = HashSet::new();
     while let Some(color) = queue.pop() {
@@ -47,7 +49,7 @@ pub fn part_one(input: &str) -> Option<usize> {
             continue;
         }
         visited.insert(color);
-        for rule in &bag_rules {
+        for rule in bag_rules {
             if rule.contains.iter().any(|(_, c)| c == color) {
                 queue.push(&rule.color);
             }
@@ -58,9 +60,7 @@ pub fn part_one(input: &str) -> Option<usize> {
     Some(visited.len() - 1)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let bag_rules = input.lines().map(BagRule::parse).collect::<Vec<BagRule>>();
-
+pub fn part_two(bag_rules: &[BagRule]) -> Option<u64> {
     let mut queue = vec![("shiny gold", 1)];
     let mut total_bags = 0;
     while let Some((color, count)) = queue.pop() {
@@ -83,13 +83,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse(&advent_of_code::template::read_file(
+            "examples", PUZZLE,
+        )));
         assert_eq!(result, Some(4));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse(&advent_of_code::template::read_file(
+            "examples", PUZZLE,
+        )));
         assert_eq!(result, Some(32));
     }
 }
@@ -1,4 +1,4 @@
-advent_of_code::solution!(8);
+advent_of_code::solution!(8, parse = State::parse);
 
 #[derive(Debug, Clone)]
 pub enum Instruction {
@@ -66,14 +66,11 @@ impl State {
     }
 }
 
-pub fn part_one(input: &str) -> Option<i64> {
-    let mut state = State::parse(input);
-
-    state.run(false)
+pub fn part_one(state: &State) -> Option<i64> {
+    state.clone().run(false)
 }
 
-pub fn part_two(input: &str) -> Option<i64> {
-    let state = State::parse(input);
+pub fn part_two(state: &State) -> Option<i64> {
     let mut acc: Option<i64> = None;
     for i in 0..state.instructions.len() {
         if let Instruction::Jmp(value) = state.instructions[i] {
@@ -101,13 +98,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&State::parse(&advent_of_code::template::read_file(
+            "examples", PUZZLE,
+        )));
         assert_eq!(result, Some(5));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&State::parse(&advent_of_code::template::read_file(
+            "examples", PUZZLE,
+        )));
         assert_eq!(result, Some(8));
     }
 }
@@ -1,4 +1,4 @@
-advent_of_code::solution!(11);
+advent_of_code::solution!(11, parse = parse);
 
 const DIRECTIONS: [(i32, i32); 8] = [
     (-1, -1),
@@ -175,7 +175,7 @@ impl Grid {
     }
 }
 
-pub fn part_one(input: &str) -> Option<usize> {
+pub fn parse(input: &str) -> Grid {
     let lines: Vec<_> = input.lines().map(|l| l.trim()).collect();
 
     let width = lines.first().unwrap().chars().collect::<Vec<_>>().len();
@@ -195,6 +195,12 @@ pub fn part_one(input: &str) -> Option<usize> {
             });
     });
 
+    grid
+}
+
+pub fn part_one(grid: &Grid) -> Option<usize> {
+    let mut grid = grid.clone();
+
     while grid.advance_state().is_some() {
         // advance
     }
@@ -202,25 +208,8 @@ pub fn part_one(input: &str) -> Option<usize> {
     Some(grid.count_occupied())
 }
 
-pub fn part_two(input: &str) -> Option<usize> {
-    let lines: Vec<_> = input.lines().map(|l| l.trim()).collect();
-
-    let width = lines.first().unwrap().chars().collect::<Vec<_>>().len();
-    let height = lines.len();
-    let mut grid = Grid::new(width, height);
-
-    lines.iter().for_each(|line| {
-        line.chars()
-            .map(|c| match c {
-                'L' => PositionType::Empty,
-                '.' => PositionType::Floor,
-                '#' => PositionType::Occupied,
-                x => unreachable!("Invalid {x:?} found while parsing"),
-            })
-            .for_each(|position_type| {
-                grid.append(position_type);
-            });
-    });
+pub fn part_two(grid: &Grid) -> Option<usize> {
+    let mut grid = grid.clone();
 
     while grid.advance_state_2().is_some() {
         // advance
@@ -235,13 +224,17 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse(&advent_of_code::template::read_file(
+            "examples", PUZZLE,
+        )));
         assert_eq!(result, Some(37));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse(&advent_of_code::template::read_file(
+            "examples", PUZZLE,
+        )));
         assert_eq!(result, Some(26));
     }
 }
@@ -47,7 +47,7 @@ pub fn part_two(input: &str) -> Option<u64> {
     let mut step: u64 = 1;
     for (offset, &bus_id) in bus_ids.iter().enumerate() {
         if let Some(bus_id) = bus_id {
-            while (time + offset as u64) % bus_id != 0 {
+            while !(time + offset as u64).is_multiple_of(bus_id) {
                 time += step;
             }
             step *= bus_id;
@@ -62,13 +62,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(295));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(1068781));
     }
 }
@@ -62,7 +62,7 @@ pub fn part_one(input: &str) -> Option<u64> {
     values.values().sum::<u64>().into()
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
+pub fn part_two(_input: &str) -> Option<u64> {
     None
 }
 
@@ -72,7 +72,7 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(165));
     }
 
@@ -82,7 +82,7 @@ mod tests {
 mem[42] = 100
 mask = 00000000000000000000000000000000X0XX
 mem[26] = 1"#;
-        // let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        // let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         let result = part_two(sample);
         assert_eq!(result, None);
     }
@@ -39,23 +39,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        assert_eq!(part_one("0,3,6"), Some(436));
-        assert_eq!(part_one("1,3,2"), Some(1));
-        assert_eq!(part_one("2,1,3"), Some(10));
-        assert_eq!(part_one("1,2,3"), Some(27));
-        assert_eq!(part_one("2,3,1"), Some(78));
-        assert_eq!(part_one("3,2,1"), Some(438));
-        assert_eq!(part_one("3,1,2"), Some(1836));
+        for example in advent_of_code::template::read_examples(PUZZLE) {
+            example.assert_part(1, part_one(&example.input));
+        }
     }
 
     #[test]
     fn test_part_two() {
-        assert_eq!(part_two("0,3,6"), Some(175594));
-        assert_eq!(part_two("1,3,2"), Some(2578));
-        assert_eq!(part_two("2,1,3"), Some(3544142));
-        assert_eq!(part_two("1,2,3"), Some(261214));
-        assert_eq!(part_two("2,3,1"), Some(6895259));
-        assert_eq!(part_two("3,2,1"), Some(18));
-        assert_eq!(part_two("3,1,2"), Some(362));
+        for example in advent_of_code::template::read_examples(PUZZLE) {
+            example.assert_part(2, part_two(&example.input));
+        }
     }
 }
@@ -1,79 +1,232 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
-use args::{parse, AppArguments};
+use advent_of_code::template::commands::{
+    all, download, examples, read, scaffold, solve, status, time, verify, watch,
+};
+use args::{parse, AppArguments, Arguments};
 
-#[cfg(feature = "today")]
-use advent_of_code::template::Day;
-#[cfg(feature = "today")]
+use advent_of_code::template::{Day, PuzzleId};
 use std::process;
 
+mod solutions {
+    use advent_of_code::template::runner::Solution;
+
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
+    use advent_of_code::template::commands::time::{Comparison, MergePolicy};
+    use advent_of_code::template::{limits::Limits, Day, DaySelection, Year};
+    use std::{process, time::Duration};
+
+    pub struct Arguments {
+        pub year: Year,
+        pub command: AppArguments,
+    }
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            days: Vec<Day>,
         },
         Read {
-            day: Day,
+            days: Vec<Day>,
         },
         Scaffold {
-            day: Day,
+            days: Vec<Day>,
             download: bool,
             overwrite: bool,
         },
         Solve {
-            day: Day,
+            days: Vec<Day>,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            limits: Limits,
         },
         All {
             release: bool,
+            isolated: bool,
+            jobs: usize,
+            limits: Limits,
         },
         Time {
             all: bool,
-            day: Option<Day>,
+            days: Option<Vec<Day>>,
             store: bool,
+            isolated: bool,
+            warmup: Option<u32>,
+            jobs: usize,
+            limits: Limits,
+            compare: Option<Comparison>,
+            save_baseline: Option<String>,
+            merge_policy: MergePolicy,
+        },
+        Verify {
+            days: Option<Vec<Day>>,
+        },
+        Examples {
+            days: Vec<Day>,
+        },
+        WatchDay {
+            day: Day,
+            release: bool,
         },
+        Status,
         #[cfg(feature = "today")]
         Today,
     }
 
-    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
+    /// Slowdown in percent above which `cargo time --compare` flags a step.
+    const DEFAULT_REGRESSION_THRESHOLD: f64 = 10.0;
+
+    /// Reject days that are not puzzle days of the targeted year.
+    fn check_day(year: Year, day: Day) -> Result<Day, String> {
+        if year.contains(day) {
+            Ok(day)
+        } else {
+            Err(format!(
+                "{year} only has {} puzzles, day {day} does not exist.",
+                year.day_count()
+            ))
+        }
+    }
+
+    /// Resolve a selection of days like `1..=5`, `3,7,11`, `all` or `unsolved`.
+    fn select_days(year: Year, selection: &DaySelection) -> Result<Vec<Day>, String> {
+        let days = selection
+            .days(year)
+            .into_iter()
+            .map(|day| check_day(year, day))
+            .collect::<Result<Vec<Day>, String>>()?;
+
+        if days.is_empty() {
+            return Err("no days selected.".into());
+        }
+
+        Ok(days)
+    }
+
+    /// Read a required selection of days.
+    fn parse_days(
+        year: Year,
+        args: &mut pico_args::Arguments,
+    ) -> Result<Vec<Day>, Box<dyn std::error::Error>> {
+        Ok(select_days(year, &args.free_from_str()?)?)
+    }
+
+    /// Read an optional selection of days.
+    fn parse_opt_days(
+        year: Year,
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<Vec<Day>>, Box<dyn std::error::Error>> {
+        Ok(args
+            .opt_free_from_str()?
+            .map(|selection| select_days(year, &selection))
+            .transpose()?)
+    }
+
+    /// Read the `--timeout` (in seconds) and `--memory-limit` (in megabytes) options.
+    fn parse_limits(args: &mut pico_args::Arguments) -> Result<Limits, pico_args::Error> {
+        Ok(Limits {
+            timeout: args
+                .opt_value_from_str("--timeout")?
+                .map(Duration::from_secs),
+            memory_limit: args.opt_value_from_str("--memory-limit")?,
+        })
+    }
+
+    pub fn parse() -> Result<Arguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
+        let subcommand = args.subcommand()?;
+
+        let year = match args.opt_value_from_str("--year")? {
+            Some(year) => year,
+            None => {
+                Year::from_env().ok_or("no year configured. Set `AOC_YEAR` or pass `--year`.")?
+            }
+        };
+
+        let app_args = match subcommand.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
+                isolated: args.contains("--isolated"),
+                jobs: args.opt_value_from_str("--jobs")?.unwrap_or(1),
+                limits: parse_limits(&mut args)?,
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let isolated = args.contains("--isolated");
+                let warmup = args.opt_value_from_str("--warmup")?;
+                // benchmarks run sequentially unless requested otherwise, so they do not disturb each other.
+                let jobs = args.opt_value_from_str("--jobs")?.unwrap_or(1);
+                let limits = parse_limits(&mut args)?;
+
+                // naming a baseline implies comparing against it.
+                let baseline: Option<String> = args.opt_value_from_str("--baseline")?;
+                let compare = if args.contains("--compare") || baseline.is_some() {
+                    Some(Comparison {
+                        baseline,
+                        threshold: args
+                            .opt_value_from_str("--threshold")?
+                            .unwrap_or(DEFAULT_REGRESSION_THRESHOLD),
+                    })
+                } else {
+                    None
+                };
+                let save_baseline = args.opt_value_from_str("--save-baseline")?;
+                let merge_policy = args.opt_value_from_str("--merge")?.unwrap_or_default();
 
                 AppArguments::Time {
                     all,
-                    day: args.opt_free_from_str()?,
+                    days: parse_opt_days(year, &mut args)?,
                     store,
+                    isolated,
+                    warmup,
+                    jobs,
+                    limits,
+                    compare,
+                    save_baseline,
+                    merge_policy,
                 }
             }
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                days: parse_days(year, &mut args)?,
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                days: parse_days(year, &mut args)?,
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                days: parse_days(year, &mut args)?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
+            Some("solve") => {
+                let days = parse_days(year, &mut args)?;
+                let submit = args.opt_value_from_str("--submit")?;
+
+                if submit.is_some() && days.len() > 1 {
+                    return Err("`--submit` can only be used with a single day.".into());
+                }
+
+                AppArguments::Solve {
+                    days,
+                    release: args.contains("--release"),
+                    submit,
+                    dhat: args.contains("--dhat"),
+                    limits: parse_limits(&mut args)?,
+                }
+            }
+            Some("verify") => AppArguments::Verify {
+                days: parse_opt_days(year, &mut args)?,
+            },
+            Some("examples") => AppArguments::Examples {
+                days: parse_days(year, &mut args)?,
+            },
+            Some("watch-day") => AppArguments::WatchDay {
+                day: check_day(year, args.free_from_str()?)?,
                 release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
             },
+            Some("status") => AppArguments::Status,
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
             Some(x) => {
@@ -91,7 +244,28 @@ mod args {
             eprintln!("Warning: unknown argument(s): {remaining:?}.");
         }
 
-        Ok(app_args)
+        Ok(Arguments {
+            year,
+            command: app_args,
+        })
+    }
+}
+
+/// Run a command for every selected day, then exit with a non-zero status if it failed for any of them.
+fn for_each_day(days: &[Day], mut handle: impl FnMut(Day) -> bool) {
+    let mut failed = 0;
+
+    for day in days {
+        if !handle(*day) {
+            failed += 1;
+        }
+    }
+
+    if failed > 0 {
+        if days.len() > 1 {
+            eprintln!("Failed for {failed} of {} days.", days.len());
+        }
+        process::exit(1);
     }
 }
 
@@ -101,39 +275,93 @@ fn main() {
             eprintln!("Error: {err}");
             std::process::exit(1);
         }
-        Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
+        Ok(Arguments { year, command }) => match command {
+            AppArguments::All {
+                release,
+                isolated,
+                jobs,
+                limits,
+            } => {
+                all::handle(solutions::SOLUTIONS, year, release, isolated, jobs, limits);
+            }
+            AppArguments::Time {
+                days,
+                all,
+                store,
+                isolated,
+                warmup,
+                jobs,
+                limits,
+                compare,
+                save_baseline,
+                merge_policy,
+            } => time::handle(
+                solutions::SOLUTIONS,
+                year,
+                days,
+                all,
+                store,
+                isolated,
+                warmup,
+                jobs,
+                limits,
+                compare,
+                save_baseline,
+                merge_policy,
+            ),
+            AppArguments::Download { days } => {
+                for_each_day(&days, |day| download::handle(PuzzleId::new(year, day)));
+            }
+            AppArguments::Read { days } => {
+                for_each_day(&days, |day| read::handle(PuzzleId::new(year, day)));
+            }
             AppArguments::Scaffold {
-                day,
+                days,
                 download,
                 overwrite,
             } => {
-                scaffold::handle(day, overwrite);
-                if download {
-                    download::handle(day);
-                }
+                for_each_day(&days, |day| {
+                    let puzzle = PuzzleId::new(year, day);
+                    scaffold::handle(puzzle, overwrite) && (!download || download::handle(puzzle))
+                });
             }
             AppArguments::Solve {
-                day,
+                days,
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                limits,
+            } => {
+                for_each_day(&days, |day| {
+                    solve::handle(PuzzleId::new(year, day), release, dhat, submit, limits)
+                });
+            }
+            AppArguments::Verify { days } => verify::handle(solutions::SOLUTIONS, year, days),
+            AppArguments::Examples { days } => {
+                for_each_day(&days, |day| {
+                    examples::handle(solutions::SOLUTIONS, PuzzleId::new(year, day))
+                });
+            }
+            AppArguments::WatchDay { day, release } => {
+                watch::handle(PuzzleId::new(year, day), release);
+            }
+            AppArguments::Status => status::handle(year),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
                     Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
+                        let puzzle = PuzzleId::new(year, day);
+                        let is_ok = scaffold::handle(puzzle, false)
+                            && download::handle(puzzle)
+                            && read::handle(puzzle);
+                        if !is_ok {
+                            process::exit(1);
+                        }
                     }
                     None => {
                         eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
+                            "`today` command can only be run on puzzle days of december \
+                            (the 1st to the 25th, or the 12th since 2025). Please use `scaffold` with a specific day."
                         );
                         process::exit(1)
                     }
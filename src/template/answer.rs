@@ -0,0 +1,180 @@
+use std::fmt::Display;
+
+use crate::template::ocr;
+
+/// The answer of a solution part.
+/// Solutions return any type that converts into an answer, see [`PartOutput`].
+///
+/// # Display
+/// Integers and text display as is, grids display one row per line.
+///
+/// ```
+/// # use advent_of_code::template::Answer;
+/// assert_eq!(Answer::from(42_u64).to_string(), "42");
+/// assert_eq!(Answer::from(-7_i32).to_string(), "-7");
+/// assert_eq!(Answer::from("abc").to_string(), "abc");
+/// assert_eq!(Answer::grid(["#.", ".#"]).to_string(), "#.\n.#");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Signed(i128),
+    Unsigned(u128),
+    Text(String),
+    /// Multi-line output, e.g. letters drawn on a grid.
+    Grid(Vec<String>),
+}
+
+impl Answer {
+    /// Creates a [`Answer::Grid`] from its rows.
+    pub fn grid<T: Display>(rows: impl IntoIterator<Item = T>) -> Self {
+        Answer::Grid(rows.into_iter().map(|row| row.to_string()).collect())
+    }
+
+    /// The string that is submitted to the website for this answer.
+    /// Multi-line answers are letters drawn on a grid, they are converted with [`ocr::recognize`].
+    /// Returns `None` if the letters can not be recognized.
+    pub fn to_submission(&self) -> Option<String> {
+        let answer = self.to_string();
+        if answer.contains('\n') {
+            ocr::recognize(&answer)
+        } else {
+            Some(answer)
+        }
+    }
+
+    /// Whether the answer matches an expected answer, e.g. an accepted answer of the website.
+    /// Multi-line answers match both the grid itself and the letters drawn on it.
+    pub fn matches(&self, expected: &str) -> bool {
+        self.to_string() == expected || self.to_submission().as_deref() == Some(expected)
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Signed(x) => write!(f, "{x}"),
+            Answer::Unsigned(x) => write!(f, "{x}"),
+            Answer::Text(x) => write!(f, "{x}"),
+            Answer::Grid(rows) => write!(f, "{}", rows.join("\n")),
+        }
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($variant:ident, $target:ty, $($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(value: $t) -> Self {
+                    Answer::$variant(value as $target)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(Signed, i128, i8, i16, i32, i64, i128, isize);
+impl_from_integer!(Unsigned, u128, u8, u16, u32, u64, u128, usize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+impl From<char> for Answer {
+    fn from(value: char) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Return types of solution parts:
+///  1. `Option<T>`, where `None` means the part is not solved yet.
+///  2. `Result<T, E>`, where the error is reported as the reason the part failed.
+///
+/// `T` is any type that converts into an [`Answer`].
+pub trait PartOutput {
+    fn into_answer(self) -> Result<Option<Answer>, String>;
+}
+
+impl<T: Into<Answer>> PartOutput for Option<T> {
+    fn into_answer(self) -> Result<Option<Answer>, String> {
+        Ok(self.map(Into::into))
+    }
+}
+
+impl<T: Into<Answer>, E: Display> PartOutput for Result<T, E> {
+    fn into_answer(self) -> Result<Option<Answer>, String> {
+        self.map(|x| Some(x.into())).map_err(|e| e.to_string())
+    }
+}
+
+impl PartOutput for Answer {
+    fn into_answer(self) -> Result<Option<Answer>, String> {
+        Ok(Some(self))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answer, PartOutput};
+
+    #[test]
+    fn converts_part_outputs() {
+        assert_eq!(Some(1_u8).into_answer(), Ok(Some(Answer::Unsigned(1))));
+        assert_eq!(None::<u64>.into_answer(), Ok(None));
+        assert_eq!(
+            Ok::<_, String>(u128::MAX).into_answer(),
+            Ok(Some(Answer::Unsigned(u128::MAX)))
+        );
+        assert_eq!(
+            Err::<i64, _>("no path found").into_answer(),
+            Err("no path found".to_string())
+        );
+    }
+
+    #[test]
+    fn converts_answers_for_submission() {
+        assert_eq!(Answer::from(-1_i64).to_submission(), Some("-1".into()));
+        assert_eq!(Answer::from("ABC").to_submission(), Some("ABC".into()));
+        assert_eq!(Answer::grid(["#.", ".#"]).to_submission(), None);
+        assert_eq!(
+            Answer::grid([
+                "#..#.###.",
+                "#..#..#..",
+                "####..#..",
+                "#..#..#..",
+                "#..#..#..",
+                "#..#.###.",
+            ])
+            .to_submission(),
+            Some("HI".into())
+        );
+    }
+
+    #[test]
+    fn matches_expected_answers() {
+        let grid = Answer::grid([
+            "#..#.###.",
+            "#..#..#..",
+            "####..#..",
+            "#..#..#..",
+            "#..#..#..",
+            "#..#.###.",
+        ]);
+        assert!(grid.matches("HI"));
+        assert!(grid.matches(&grid.to_string()));
+        assert!(!grid.matches("HJ"));
+        assert!(Answer::from(42_u32).matches("42"));
+        assert!(!Answer::from(42_u32).matches("43"));
+    }
+}
@@ -0,0 +1,225 @@
+/// Accepted answers of solved puzzles, used to check solutions against the real input.
+/// Answers for each day live in a JSON file in `data/<year>/answers`, e.g. `data/2020/answers/01.json`:
+/// `{ "part_1": "514579", "part_2": null }`.
+/// They are recorded from the "Your puzzle answer was" lines of downloaded puzzle descriptions, see [`Answers::record`].
+use std::{collections::HashMap, fs, io, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{data_dir, PuzzleId};
+
+static ANSWERS_DIR: &str = "answers";
+
+/// Accepted answers for both parts of a day.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl Answers {
+    /// Read the answers of a day. If no answers were recorded, returns empty answers.
+    pub fn read(puzzle: PuzzleId) -> Result<Self, String> {
+        match fs::read_to_string(get_path(puzzle)) {
+            Ok(s) => Answers::try_from(s),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Store the answers of a day, overwriting existing answers.
+    pub fn store(&self, puzzle: PuzzleId) -> Result<(), io::Error> {
+        fs::create_dir_all(format!("{}/{ANSWERS_DIR}", data_dir(puzzle.year)))?;
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(get_path(puzzle))?;
+        json.format_to(&mut file)
+    }
+
+    /// Read the accepted answers from the downloaded description of a puzzle.
+    /// Parts that are not solved yet have no answer.
+    pub fn from_puzzle(puzzle: PuzzleId) -> Result<Self, String> {
+        fs::read_to_string(puzzle.data_path("puzzles", "md"))
+            .map(|markdown| Answers::parse_puzzle(&markdown))
+            .map_err(|e| e.to_string())
+    }
+
+    /// The accepted answers of a day: those of the downloaded description, or the recorded ones if it has none.
+    pub fn accepted(puzzle: PuzzleId) -> Self {
+        match Answers::from_puzzle(puzzle) {
+            Ok(answers) if answers != Answers::default() => answers,
+            _ => Answers::read(puzzle).unwrap_or_default(),
+        }
+    }
+
+    /// Whether both parts have an answer.
+    pub fn is_complete(&self) -> bool {
+        self.part_1.is_some() && self.part_2.is_some()
+    }
+
+    /// Store the accepted answers found in the downloaded description of a puzzle.
+    /// Answers of the description take precedence over stored ones. Returns whether the stored answers changed.
+    pub fn record(puzzle: PuzzleId) -> Result<bool, String> {
+        let stored = Answers::read(puzzle)?;
+        let accepted = Answers::from_puzzle(puzzle)?;
+
+        let answers = Answers {
+            part_1: accepted.part_1.or_else(|| stored.part_1.clone()),
+            part_2: accepted.part_2.or_else(|| stored.part_2.clone()),
+        };
+
+        if answers == stored {
+            return Ok(false);
+        }
+
+        answers.store(puzzle).map_err(|e| e.to_string())?;
+        Ok(true)
+    }
+
+    /// Parse the "Your puzzle answer was `42`." lines of a puzzle description, in order of the parts.
+    fn parse_puzzle(markdown: &str) -> Self {
+        let mut answers = markdown.lines().filter_map(|line| {
+            let answer = line.trim().strip_prefix("Your puzzle answer was")?.trim();
+            let answer = match answer.split('`').nth(1) {
+                Some(code) => code,
+                None => answer.trim_end_matches('.'),
+            };
+            Some(answer.trim_matches('*').to_string()).filter(|a| !a.is_empty())
+        });
+
+        Answers {
+            part_1: answers.next(),
+            part_2: answers.next(),
+        }
+    }
+
+    /// Get the answer of a part (1 or 2).
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+fn get_path(puzzle: PuzzleId) -> String {
+    puzzle.data_path(ANSWERS_DIR, "json")
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answers> for JsonValue {
+    fn from(value: &Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "part_1".into(),
+            value
+                .part_1
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        map.insert(
+            "part_2".into(),
+            value
+                .part_2
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let read_part = |key: &str| match json.get(key) {
+            None => Ok(None),
+            Some(v) if v.is_null() => Ok(None),
+            Some(v) => v
+                .get::<String>()
+                .cloned()
+                .map(Some)
+                .ok_or(format!("expected `{key}` to be null or string.")),
+        };
+
+        Ok(Answers {
+            part_1: read_part("part_1")?,
+            part_2: read_part("part_2")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Answers;
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn handles_json_answers() {
+        let json = r#"{ "part_1": "514579", "part_2": null }"#.to_string();
+        let answers = Answers::try_from(json).unwrap();
+        assert_eq!(answers.get(1), Some("514579"));
+        assert_eq!(answers.get(2), None);
+    }
+
+    #[test]
+    fn handles_missing_parts() {
+        let answers = Answers::try_from("{}".to_string()).unwrap();
+        assert_eq!(answers, Answers::default());
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_numeric_answers() {
+        Answers::try_from(r#"{ "part_1": 514579 }"#.to_string()).unwrap();
+    }
+
+    #[test]
+    fn parses_answers_of_puzzle_descriptions() {
+        let markdown = "\
+## --- Day 1: Report Repair ---
+
+The correct answer is `*514579*`.
+
+Your puzzle answer was `1007104`.
+
+## --- Part Two ---
+
+Your puzzle answer was `18847752`.
+
+Both parts of this puzzle are complete! They provide two gold stars: **
+";
+        assert_eq!(
+            Answers::parse_puzzle(markdown),
+            Answers {
+                part_1: Some("1007104".into()),
+                part_2: Some("18847752".into()),
+            }
+        );
+
+        let markdown = "Your puzzle answer was ABCDEFGH.\n";
+        assert_eq!(Answers::parse_puzzle(markdown).get(1), Some("ABCDEFGH"));
+        assert_eq!(Answers::parse_puzzle("Part one").get(1), None);
+    }
+
+    #[test]
+    fn roundtrips_answers() {
+        let answers = Answers {
+            part_1: Some("1".into()),
+            part_2: Some("ABCDEFGH".into()),
+        };
+        let json = JsonValue::from(&answers).stringify().unwrap();
+        assert_eq!(Answers::try_from(json).unwrap(), answers);
+    }
+}
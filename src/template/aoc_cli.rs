@@ -1,16 +1,19 @@
 /// Wrapper module around the "aoc-cli" command-line.
 use std::{
     fmt::Display,
+    fs, io,
+    path::Path,
     process::{Command, Output, Stdio},
 };
 
-use crate::template::Day;
+use crate::template::PuzzleId;
 
 #[derive(Debug)]
 pub enum AocCommandError {
     CommandNotFound,
     CommandNotCallable,
     BadExitStatus(Output),
+    IO(io::Error),
 }
 
 impl Display for AocCommandError {
@@ -21,6 +24,7 @@ impl Display for AocCommandError {
             AocCommandError::BadExitStatus(_) => {
                 write!(f, "aoc-cli exited with a non-zero status.")
             }
+            AocCommandError::IO(e) => write!(f, "{e}"),
         }
     }
 }
@@ -33,25 +37,21 @@ pub fn check() -> Result<(), AocCommandError> {
     Ok(())
 }
 
-pub fn read(day: Day) -> Result<Output, AocCommandError> {
-    let puzzle_path = get_puzzle_path(day);
+pub fn read(puzzle: PuzzleId) -> Result<Output, AocCommandError> {
+    let puzzle_path = get_puzzle_path(puzzle);
+    create_parent_dir(&puzzle_path)?;
 
-    let args = build_args(
-        "read",
-        &[
-            "--description-only".into(),
-            "--puzzle-file".into(),
-            puzzle_path,
-        ],
-        day,
-    );
+    // NOTE: `--description-only` would drop the "Your puzzle answer was" lines that answers are recorded from.
+    let args = build_args("read", &["--puzzle-file".into(), puzzle_path], puzzle);
 
     call_aoc_cli(&args)
 }
 
-pub fn download(day: Day) -> Result<Output, AocCommandError> {
-    let input_path = get_input_path(day);
-    let puzzle_path = get_puzzle_path(day);
+pub fn download(puzzle: PuzzleId) -> Result<Output, AocCommandError> {
+    let input_path = get_input_path(puzzle);
+    let puzzle_path = get_puzzle_path(puzzle);
+    create_parent_dir(&input_path)?;
+    create_parent_dir(&puzzle_path)?;
 
     let args = build_args(
         "download",
@@ -62,7 +62,7 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
             "--puzzle-file".into(),
             puzzle_path.to_string(),
         ],
-        day,
+        puzzle,
     );
 
     let output = call_aoc_cli(&args)?;
@@ -72,47 +72,56 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
     Ok(output)
 }
 
-pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
+pub fn submit(puzzle: PuzzleId, part: u8, result: &str) -> Result<Output, AocCommandError> {
     // workaround: the argument order is inverted for submit.
-    let mut args = build_args("submit", &[], day);
+    let mut args = build_args("submit", &[], puzzle);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
+
+    // the response is captured so the verdict can be recorded, see `submissions`.
+    let output = call_aoc_cli_with(&args, Stdio::piped())?;
+    print!("{}", String::from_utf8_lossy(&output.stdout));
+    Ok(output)
 }
 
-fn get_input_path(day: Day) -> String {
-    format!("data/inputs/{day}.txt")
+fn get_input_path(puzzle: PuzzleId) -> String {
+    puzzle.data_path("inputs", "txt")
 }
 
-fn get_puzzle_path(day: Day) -> String {
-    format!("data/puzzles/{day}.md")
+fn get_puzzle_path(puzzle: PuzzleId) -> String {
+    puzzle.data_path("puzzles", "md")
 }
 
-fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
+fn create_parent_dir(path: &str) -> Result<(), AocCommandError> {
+    match Path::new(path).parent() {
+        Some(dir) => fs::create_dir_all(dir).map_err(AocCommandError::IO),
+        None => Ok(()),
     }
 }
 
-fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
+fn build_args(command: &str, args: &[String], puzzle: PuzzleId) -> Vec<String> {
     let mut cmd_args = args.to_vec();
 
-    if let Some(year) = get_year() {
-        cmd_args.push("--year".into());
-        cmd_args.push(year.to_string());
-    }
-
-    cmd_args.append(&mut vec!["--day".into(), day.to_string(), command.into()]);
+    cmd_args.append(&mut vec![
+        "--year".into(),
+        puzzle.year.to_string(),
+        "--day".into(),
+        puzzle.day.to_string(),
+        command.into(),
+    ]);
 
     cmd_args
 }
 
 fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
+    call_aoc_cli_with(args, Stdio::inherit())
+}
+
+fn call_aoc_cli_with(args: &[String], stdout: Stdio) -> Result<Output, AocCommandError> {
     // println!("Calling >aoc with: {}", args.join(" "));
     let output = Command::new("aoc")
         .args(args)
-        .stdout(Stdio::inherit())
+        .stdout(stdout)
         .stderr(Stdio::inherit())
         .output()
         .map_err(|_| AocCommandError::CommandNotCallable)?;
@@ -0,0 +1,351 @@
+/// Built-in client for the Advent of Code website.
+/// Used for `download`, `read` and `submit` unless the "aoc-cli" backend is selected, see [`Backend`].
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::template::{markdown, PuzzleId};
+
+static DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// Tool that is used to interact with the Advent of Code website.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Backend {
+    /// The built-in [`AocClient`].
+    Native,
+    /// The external `aoc` command of the "aoc-cli" crate.
+    AocCli,
+}
+
+impl Backend {
+    /// Reads the backend from the `AOC_BACKEND` environment variable, defaulting to [`Backend::Native`].
+    pub fn from_env() -> Self {
+        match env::var("AOC_BACKEND").as_deref() {
+            Ok("aoc-cli") => Backend::AocCli,
+            _ => Backend::Native,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ClientError {
+    MissingSession,
+    BadStatus(u16, String),
+    Transport(String),
+    IO(io::Error),
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set `AOC_SESSION` or create the file \"~/.adventofcode.session\"."
+            ),
+            ClientError::BadStatus(status, body) => {
+                write!(f, "server responded with status {status}: {}", body.trim())
+            }
+            ClientError::Transport(e) => write!(f, "request failed: {e}"),
+            ClientError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for ClientError {
+    fn from(e: io::Error) -> Self {
+        ClientError::IO(e)
+    }
+}
+
+impl From<ureq::Error> for ClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(status, response) => {
+                ClientError::BadStatus(status, response.into_string().unwrap_or_default())
+            }
+            ureq::Error::Transport(e) => ClientError::Transport(e.to_string()),
+        }
+    }
+}
+
+/// Identifies this tool and its maintainer, as the website asks of automated clients.
+/// Taken from the `AOC_USER_AGENT` environment variable, or the `repository` and `authors` of `Cargo.toml`.
+fn user_agent() -> String {
+    match env::var("AOC_USER_AGENT") {
+        Ok(agent) if !agent.trim().is_empty() => agent,
+        _ => format_user_agent(env!("CARGO_PKG_REPOSITORY"), env!("CARGO_PKG_AUTHORS")),
+    }
+}
+
+/// e.g. `github.com/user/advent-of-code by Jane Doe <jane@example.com>`.
+fn format_user_agent(repository: &str, authors: &str) -> String {
+    let repository = repository
+        .trim_start_matches("https://")
+        .trim_start_matches("http://");
+    let tool = if repository.is_empty() {
+        env!("CARGO_PKG_NAME")
+    } else {
+        repository
+    };
+
+    // cargo joins multiple authors with colons.
+    format!("{tool} by {}", authors.replace(':', ", "))
+}
+
+/// HTTP client for the Advent of Code website.
+pub struct AocClient {
+    agent: ureq::Agent,
+    base_url: String,
+    session: String,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            agent: ureq::AgentBuilder::new().user_agent(&user_agent()).build(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+        }
+    }
+
+    /// Creates a client from the environment:
+    ///  1. the session cookie is read from `AOC_SESSION` or the file `~/.adventofcode.session`.
+    ///  2. the base URL is read from `AOC_BASE_URL`, defaulting to the Advent of Code website.
+    pub fn from_env() -> Result<Self, ClientError> {
+        let session = env::var("AOC_SESSION")
+            .ok()
+            .or_else(read_session_file)
+            .filter(|s| !s.trim().is_empty())
+            .ok_or(ClientError::MissingSession)?;
+
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+
+        Ok(Self::new(&base_url, &session))
+    }
+
+    /// Fetch the personal puzzle input of a day.
+    pub fn input(&self, puzzle: PuzzleId) -> Result<String, ClientError> {
+        let url = format!("{}/input", self.day_url(puzzle));
+        self.get(&url)
+    }
+
+    /// Fetch the puzzle description of a day as markdown.
+    pub fn puzzle(&self, puzzle: PuzzleId) -> Result<String, ClientError> {
+        let html = self.get(&self.day_url(puzzle))?;
+        Ok(markdown::puzzle_to_markdown(&html))
+    }
+
+    /// Submit an answer, returns the response message of the server as markdown.
+    pub fn submit(&self, puzzle: PuzzleId, part: u8, answer: &str) -> Result<String, ClientError> {
+        let url = format!("{}/answer", self.day_url(puzzle));
+        let level = part.to_string();
+        let html = self
+            .agent
+            .post(&url)
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &level), ("answer", answer)])?
+            .into_string()?;
+        Ok(markdown::puzzle_to_markdown(&html))
+    }
+
+    /// Download input and puzzle description of a day to the `data` directory.
+    pub fn download(&self, puzzle: PuzzleId) -> Result<(), ClientError> {
+        let input_path = get_input_path(puzzle);
+        let puzzle_path = get_puzzle_path(puzzle);
+
+        let input = self.input(puzzle)?;
+        let puzzle = self.puzzle(puzzle)?;
+
+        create_parent_dir(&input_path)?;
+        create_parent_dir(&puzzle_path)?;
+        fs::write(&input_path, input)?;
+        fs::write(&puzzle_path, puzzle)?;
+
+        println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+        println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+        Ok(())
+    }
+
+    /// Fetch the puzzle description of a day, store it and print it to the command-line.
+    pub fn read(&self, puzzle: PuzzleId) -> Result<(), ClientError> {
+        let puzzle_path = get_puzzle_path(puzzle);
+        let puzzle = self.puzzle(puzzle)?;
+        create_parent_dir(&puzzle_path)?;
+        fs::write(puzzle_path, &puzzle)?;
+        println!("{puzzle}");
+        Ok(())
+    }
+
+    fn day_url(&self, puzzle: PuzzleId) -> String {
+        format!(
+            "{}/{}/day/{}",
+            self.base_url,
+            puzzle.year,
+            puzzle.day.into_inner()
+        )
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    fn get(&self, url: &str) -> Result<String, ClientError> {
+        Ok(self
+            .agent
+            .get(url)
+            .set("Cookie", &self.cookie())
+            .call()?
+            .into_string()?)
+    }
+}
+
+fn read_session_file() -> Option<String> {
+    let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"))?;
+    fs::read_to_string(PathBuf::from(home).join(".adventofcode.session")).ok()
+}
+
+fn get_input_path(puzzle: PuzzleId) -> String {
+    puzzle.data_path("inputs", "txt")
+}
+
+fn get_puzzle_path(puzzle: PuzzleId) -> String {
+    puzzle.data_path("puzzles", "md")
+}
+
+fn create_parent_dir(path: &str) -> Result<(), io::Error> {
+    match Path::new(path).parent() {
+        Some(dir) => fs::create_dir_all(dir),
+        None => Ok(()),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_user_agent, AocClient, ClientError};
+    use crate::template::PuzzleId;
+    use crate::{day, year};
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+    };
+
+    /// Serves a single request with the given response body and reports the request line, cookie and body.
+    fn mock_server(status: u16, body: &'static str) -> (String, mpsc::Receiver<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request = vec![];
+            let mut content_length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                let line = line.trim_end().to_string();
+                if line.is_empty() {
+                    break;
+                }
+                if let Some(len) = line.to_lowercase().strip_prefix("content-length: ") {
+                    content_length = len.parse().unwrap();
+                }
+                request.push(line);
+            }
+
+            let mut content = vec![0; content_length];
+            reader.read_exact(&mut content).unwrap();
+            request.push(String::from_utf8(content).unwrap());
+
+            let mut stream = stream;
+            write!(
+                stream,
+                "HTTP/1.1 {status} OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+            tx.send(request).unwrap();
+        });
+
+        (url, rx)
+    }
+
+    #[test]
+    fn fetches_input() {
+        let (url, rx) = mock_server(200, "1721\n979\n");
+        let client = AocClient::new(&url, "abc\n");
+        assert_eq!(
+            client.input(PuzzleId::new(year!(2020), day!(1))).unwrap(),
+            "1721\n979\n"
+        );
+        let request = rx.recv().unwrap();
+        assert_eq!(request[0], "GET /2020/day/1/input HTTP/1.1");
+        assert!(request.iter().any(|l| l == "Cookie: session=abc"));
+        assert!(request
+            .iter()
+            .any(|l| l.to_lowercase().starts_with("user-agent: ") && l.contains(" by ")));
+    }
+
+    #[test]
+    fn formats_user_agents() {
+        assert_eq!(
+            format_user_agent("https://github.com/user/aoc", "Jane Doe <jane@example.com>"),
+            "github.com/user/aoc by Jane Doe <jane@example.com>"
+        );
+        assert_eq!(
+            format_user_agent("", "Jane:John"),
+            "advent_of_code by Jane, John"
+        );
+    }
+
+    #[test]
+    fn fetches_puzzle_as_markdown() {
+        let (url, _rx) = mock_server(
+            200,
+            "<main><article><h2>--- Day 8: Test ---</h2><p>Hi <em>there</em>.</p></article></main>",
+        );
+        let client = AocClient::new(&url, "abc");
+        assert_eq!(
+            client.puzzle(PuzzleId::new(year!(2020), day!(8))).unwrap(),
+            "## --- Day 8: Test ---\n\nHi *there*.\n"
+        );
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (url, rx) = mock_server(
+            200,
+            "<main><article><p>That's the right answer!</p></article></main>",
+        );
+        let client = AocClient::new(&url, "abc");
+        assert_eq!(
+            client
+                .submit(PuzzleId::new(year!(2020), day!(2)), 1, "42")
+                .unwrap(),
+            "That's the right answer!\n"
+        );
+        let request = rx.recv().unwrap();
+        assert_eq!(request[0], "POST /2020/day/2/answer HTTP/1.1");
+        assert_eq!(request.last().unwrap(), "level=1&answer=42");
+    }
+
+    #[test]
+    fn reports_bad_status() {
+        let (url, _rx) = mock_server(400, "Puzzle inputs differ by user.");
+        let client = AocClient::new(&url, "abc");
+        match client.input(PuzzleId::new(year!(2020), day!(1))) {
+            Err(ClientError::BadStatus(400, body)) => {
+                assert_eq!(body, "Puzzle inputs differ by user.")
+            }
+            _ => panic!("expected a bad status error"),
+        }
+    }
+}
@@ -0,0 +1,324 @@
+//! Performance budgets that are checked by `cargo time`.
+//!
+//! Budgets of a year are configured in `data/<year>/budgets.json`, e.g.
+//! `{ "total": "1s", "day": "100ms", "part": "50ms", "days": { "05": { "total": "250ms", "part_2": "200ms" } } }`.
+//! `total` caps the sum of all stored days, `day` and `part` are the defaults for every day and part.
+//! Entries of `days` override them for a single day and may also cap its `parse` step.
+use std::{collections::HashMap, fs, io, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    data_dir,
+    stats::{format_nanos, parse_nanos},
+    timings::{StepTiming, Timing, Timings},
+    Day, Year,
+};
+
+static BUDGETS_FILE_NAME: &str = "budgets.json";
+
+/// Budgets of a single day in nanoseconds.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DayBudget {
+    pub total: Option<f64>,
+    pub parse: Option<f64>,
+    pub part_1: Option<f64>,
+    pub part_2: Option<f64>,
+}
+
+/// Budgets of a year in nanoseconds.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Budgets {
+    pub total: Option<f64>,
+    /// Default budget of every day.
+    pub day: Option<f64>,
+    /// Default budget of every part.
+    pub part: Option<f64>,
+    pub days: HashMap<Day, DayBudget>,
+}
+
+/// A budget that was exceeded.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Violation {
+    /// What exceeded its budget, e.g. `Total`, `Day 05` or `Day 05 Part 1`.
+    pub subject: String,
+    /// The measured time in nanoseconds, `None` if the part did not finish.
+    pub nanos: Option<f64>,
+    pub budget: f64,
+}
+
+impl Violation {
+    pub fn describe(&self) -> String {
+        let budget = format_nanos(self.budget);
+        match self.nanos {
+            Some(nanos) => format!("{}: {} > {budget}", self.subject, format_nanos(nanos)),
+            None => format!("{}: did not finish, budget {budget}", self.subject),
+        }
+    }
+}
+
+impl Budgets {
+    /// Read the budgets of a year. If not present, returns `None`.
+    pub fn read(year: Year) -> Result<Option<Self>, String> {
+        match fs::read_to_string(get_path(year)) {
+            Ok(contents) => Budgets::try_from(contents).map(Some),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Check the timings of a run against the budgets.
+    /// Day and part budgets apply to the days of `run`, the total budget to `year_total` in nanoseconds.
+    pub fn check(&self, run: &Timings, year_total: f64) -> Vec<Violation> {
+        let mut violations = vec![];
+
+        for timing in &run.data {
+            let day_budget = self.days.get(&timing.day).cloned().unwrap_or_default();
+
+            if let Some(budget) = day_budget.total.or(self.day) {
+                if timing.total_nanos > budget {
+                    violations.push(Violation {
+                        subject: format!("Day {}", timing.day),
+                        nanos: Some(timing.total_nanos),
+                        budget,
+                    });
+                }
+            }
+
+            for (name, step, budget, has_failed) in [
+                (
+                    "Parse",
+                    timing.parse,
+                    day_budget.parse,
+                    timing.parse_status.is_some(),
+                ),
+                (
+                    "Part 1",
+                    timing.part_1,
+                    day_budget.part_1.or(self.part),
+                    timing.part_1_status.is_some(),
+                ),
+                (
+                    "Part 2",
+                    timing.part_2,
+                    day_budget.part_2.or(self.part),
+                    timing.part_2_status.is_some(),
+                ),
+            ] {
+                if let Some(violation) = check_step(timing, name, step, budget, has_failed) {
+                    violations.push(violation);
+                }
+            }
+        }
+
+        if let Some(budget) = self.total {
+            if year_total > budget {
+                violations.push(Violation {
+                    subject: "Total".into(),
+                    nanos: Some(year_total),
+                    budget,
+                });
+            }
+        }
+
+        violations
+    }
+}
+
+fn check_step(
+    timing: &Timing,
+    name: &str,
+    step: Option<StepTiming>,
+    budget: Option<f64>,
+    has_failed: bool,
+) -> Option<Violation> {
+    let budget = budget?;
+    let nanos = step.map(|s| s.nanos);
+
+    // a step that timed out or panicked can not be within its budget.
+    let is_exceeded = has_failed || nanos.is_some_and(|nanos| nanos > budget);
+
+    is_exceeded.then(|| Violation {
+        subject: format!("Day {} {name}", timing.day),
+        nanos: nanos.filter(|_| !has_failed),
+        budget,
+    })
+}
+
+fn get_path(year: Year) -> String {
+    format!("{}/{BUDGETS_FILE_NAME}", data_dir(year))
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl TryFrom<String> for Budgets {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let mut days = HashMap::new();
+
+        if let Some(value) = json.get("days") {
+            let entries = value
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("expected `budgets.days` to be an object.")?;
+
+            for (key, value) in entries {
+                let day =
+                    Day::from_str(key).map_err(|_| format!("expected `{key}` to be a day."))?;
+                let budget = value
+                    .get::<HashMap<String, JsonValue>>()
+                    .ok_or(format!("expected `budgets.days.{key}` to be an object."))?;
+
+                let read = |name: &str| read_budget(budget, name, &format!("days.{key}.{name}"));
+
+                days.insert(
+                    day,
+                    DayBudget {
+                        total: read("total")?,
+                        parse: read("parse")?,
+                        part_1: read("part_1")?,
+                        part_2: read("part_2")?,
+                    },
+                );
+            }
+        }
+
+        Ok(Budgets {
+            total: read_budget(json, "total", "total")?,
+            day: read_budget(json, "day", "day")?,
+            part: read_budget(json, "part", "part")?,
+            days,
+        })
+    }
+}
+
+fn read_budget(
+    json: &HashMap<String, JsonValue>,
+    key: &str,
+    path: &str,
+) -> Result<Option<f64>, String> {
+    match json.get(key) {
+        Some(value) if !value.is_null() => value
+            .get::<String>()
+            .and_then(|v| parse_nanos(v))
+            .map(Some)
+            .ok_or(format!(
+                "expected `budgets.{path}` to be a duration like `250ms`."
+            )),
+        _ => Ok(None),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Budgets, DayBudget};
+    use crate::day;
+    use crate::template::runner::PartStatus;
+    use crate::template::timings::{StepTiming, Timing, Timings};
+
+    fn timing(day: u8, part_1: f64, part_2: Option<f64>) -> Timing {
+        let step = |nanos| StepTiming { nanos, samples: 1 };
+
+        Timing {
+            day: crate::template::Day::new(day).unwrap(),
+            parse: None,
+            part_1: Some(step(part_1)),
+            part_2: part_2.map(step),
+            parse_stats: None,
+            part_1_stats: None,
+            part_2_stats: None,
+            parse_status: None,
+            part_1_status: None,
+            part_2_status: None,
+            total_nanos: part_1 + part_2.unwrap_or(0.0),
+        }
+    }
+
+    #[test]
+    fn parses_budgets() {
+        let json = r#"{ "total": "1s", "part": "50ms", "days": { "05": { "total": "250ms", "parse": "10µs" } } }"#;
+        let budgets = Budgets::try_from(json.to_string()).unwrap();
+        assert_eq!(budgets.total, Some(1e9));
+        assert_eq!(budgets.day, None);
+        assert_eq!(budgets.part, Some(5e7));
+        assert_eq!(
+            budgets.days.get(&day!(5)),
+            Some(&DayBudget {
+                total: Some(2.5e8),
+                parse: Some(1e4),
+                part_1: None,
+                part_2: None,
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_budgets() {
+        for json in [
+            r#"{ "total": 1000 }"#,
+            r#"{ "part": "1 hour" }"#,
+            r#"{ "days": { "26": {} } }"#,
+            r#"{ "days": { "01": { "part_1": true } } }"#,
+        ] {
+            assert!(Budgets::try_from(json.to_string()).is_err(), "{json}");
+        }
+    }
+
+    #[test]
+    fn checks_budgets() {
+        let budgets = Budgets {
+            total: Some(1000.0),
+            day: Some(300.0),
+            part: Some(200.0),
+            days: [(
+                day!(2),
+                DayBudget {
+                    part_2: Some(50.0),
+                    ..DayBudget::default()
+                },
+            )]
+            .into_iter()
+            .collect(),
+        };
+
+        let mut timed_out = timing(3, 10.0, None);
+        timed_out.part_2_status = Some(PartStatus::TimedOut);
+
+        let run = Timings {
+            data: vec![
+                timing(1, 100.0, Some(150.0)),
+                timing(2, 250.0, Some(100.0)),
+                timed_out,
+            ],
+            environment: None,
+        };
+
+        let violations = budgets.check(&run, 1100.0);
+        let subjects: Vec<&str> = violations.iter().map(|v| v.subject.as_str()).collect();
+        assert_eq!(
+            subjects,
+            vec![
+                "Day 02",
+                "Day 02 Part 1",
+                "Day 02 Part 2",
+                "Day 03 Part 2",
+                "Total"
+            ]
+        );
+        assert_eq!(violations[0].nanos, Some(350.0));
+        assert_eq!(violations[3].nanos, None);
+        assert_eq!(
+            violations[3].describe(),
+            "Day 03 Part 2: did not finish, budget 200.0ns"
+        );
+
+        assert!(Budgets::default().check(&run, 1100.0).is_empty());
+    }
+}
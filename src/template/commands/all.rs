@@ -1,5 +1,27 @@
-use crate::template::{all_days, run_multi::run_multi};
+use crate::template::limits::Limits;
+use crate::template::run_multi::{run_multi, Executor};
+use crate::template::runner::{RunOptions, Solution};
+use crate::template::Year;
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+pub fn handle(
+    solutions: &[Solution],
+    year: Year,
+    is_release: bool,
+    is_isolated: bool,
+    jobs: usize,
+    limits: Limits,
+) {
+    // limits can only be enforced for solutions that run in a child process.
+    let executor = Executor::select(solutions, year, is_isolated || limits.is_set(), is_release);
+
+    run_multi(
+        year,
+        &year.days().collect(),
+        executor,
+        RunOptions {
+            limits,
+            ..RunOptions::default()
+        },
+        jobs,
+    );
 }
@@ -1,14 +1,52 @@
-use crate::template::{aoc_cli, Day};
+use crate::template::answers::Answers;
+use crate::template::aoc_client::{AocClient, Backend};
+use crate::template::{aoc_cli, examples, PuzzleId};
 use std::process;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+/// Returns whether the puzzle could be downloaded, setup errors exit immediately.
+pub fn handle(puzzle: PuzzleId) -> bool {
+    match Backend::from_env() {
+        Backend::Native => {
+            let client = AocClient::from_env().unwrap_or_else(|e| {
+                eprintln!("could not create Advent of Code client: {e}");
+                process::exit(1);
+            });
+
+            if let Err(e) = client.download(puzzle) {
+                eprintln!("failed to download {puzzle}: {e}");
+                return false;
+            };
+        }
+        Backend::AocCli => {
+            if aoc_cli::check().is_err() {
+                eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+                process::exit(1);
+            }
+
+            if let Err(e) = aoc_cli::download(puzzle) {
+                eprintln!("failed to call aoc-cli: {e}");
+                return false;
+            };
+        }
+    }
+
+    if let Err(e) = examples::apply(puzzle) {
+        eprintln!("could not extract example from puzzle description: {e}");
     }
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+    record_answers(puzzle);
+
+    true
+}
+
+/// Store the answers of solved parts, so `cargo verify` can check them.
+pub fn record_answers(puzzle: PuzzleId) {
+    match Answers::record(puzzle) {
+        Ok(true) => println!(
+            "🎄 Recorded accepted answers in \"{}\".",
+            puzzle.data_path("answers", "json")
+        ),
+        Ok(false) => {}
+        Err(e) => eprintln!("could not record accepted answers: {e}"),
+    }
 }
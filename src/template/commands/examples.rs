@@ -0,0 +1,103 @@
+use std::{panic, process};
+
+use crate::template::examples;
+use crate::template::runner::{DayResult, OutputFormat, PartResult, RunOptions, Solution};
+use crate::template::{Answer, PuzzleId, ANSI_BOLD, ANSI_GREEN, ANSI_RED, ANSI_RESET, ANSI_YELLOW};
+
+/// Run the examples of a day, returns whether none of them failed.
+pub fn handle(solutions: &[Solution], puzzle: PuzzleId) -> bool {
+    if solutions.is_empty() {
+        eprintln!("No solutions are compiled into the main binary, e.g. because it was built with the `dhat-heap` feature.");
+        process::exit(1);
+    }
+
+    let Some(solution) = solutions.iter().find(|s| s.puzzle == puzzle) else {
+        eprintln!("No solution found for {puzzle}.");
+        return false;
+    };
+
+    let examples = match examples::read(puzzle) {
+        Ok(examples) => examples,
+        Err(e) => {
+            eprintln!("Could not read examples of {puzzle}: {e}");
+            return false;
+        }
+    };
+
+    let options = RunOptions {
+        format: OutputFormat::Quiet,
+        ..RunOptions::default()
+    };
+
+    let width = examples.iter().map(|e| e.name.len()).max().unwrap_or(0);
+    let (mut passed, mut failed) = (0, 0);
+
+    for example in &examples {
+        // a panicking solution should not abort the remaining examples.
+        let result = panic::catch_unwind(|| (solution.run)(&example.input, options)).ok();
+
+        for part in 1..=2 {
+            let Some(expected) = example.expected(part) else {
+                continue;
+            };
+
+            let answer = result
+                .as_ref()
+                .and_then(|r| r.parts.iter().find(|r| r.part == part));
+
+            let status = match answer.and_then(|r| r.answer.as_deref()).map(Answer::from) {
+                Some(answer) if answer.matches(expected) => {
+                    passed += 1;
+                    format!("{ANSI_GREEN}pass{ANSI_RESET}  {expected}")
+                }
+                Some(answer) => {
+                    failed += 1;
+                    // grids are shown as the letters drawn on them.
+                    let answer = answer.to_submission().unwrap_or_else(|| answer.to_string());
+                    format!("{ANSI_RED}FAIL{ANSI_RESET}  got {answer}, expected {expected}")
+                }
+                None => {
+                    failed += 1;
+                    format!(
+                        "{ANSI_RED}FAIL{ANSI_RESET}  {}, expected {expected}",
+                        describe_missing(result.as_ref(), answer)
+                    )
+                }
+            };
+
+            println!(
+                "{ANSI_BOLD}{:<width$}{ANSI_RESET}  Part {part}  {status}",
+                example.name
+            );
+        }
+    }
+
+    if passed + failed == 0 {
+        let manifest_path = puzzle.data_path("examples", "json");
+        println!(
+            "{ANSI_YELLOW}No expected answers found.{ANSI_RESET} Add them to \"{manifest_path}\"."
+        );
+        return true;
+    }
+
+    println!("\n{ANSI_BOLD}Examples:{ANSI_RESET} {passed} passed, {failed} failed");
+
+    failed == 0
+}
+
+fn describe_missing(day: Option<&DayResult>, result: Option<&PartResult>) -> String {
+    let parse = day.and_then(|d| d.parse.as_ref());
+
+    match (result, parse.and_then(|p| p.status)) {
+        (Some(result), _) => match &result.error {
+            Some(error) => format!("{}: {error}", result.status.label()),
+            None => result.status.label().to_string(),
+        },
+        (None, Some(status)) => match parse.and_then(|p| p.error.as_ref()) {
+            Some(error) => format!("parse {}: {error}", status.label()),
+            None => format!("parse {}", status.label()),
+        },
+        (None, None) if day.is_none() => "panicked".into(),
+        (None, None) => "not run".into(),
+    }
+}
@@ -1,15 +1,37 @@
 use std::process;
 
-use crate::template::{aoc_cli, Day};
+use crate::template::aoc_client::{AocClient, Backend};
+use crate::template::commands::download::record_answers;
+use crate::template::{aoc_cli, PuzzleId};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+/// Returns whether the puzzle could be read, setup errors exit immediately.
+pub fn handle(puzzle: PuzzleId) -> bool {
+    match Backend::from_env() {
+        Backend::Native => {
+            let client = AocClient::from_env().unwrap_or_else(|e| {
+                eprintln!("could not create Advent of Code client: {e}");
+                process::exit(1);
+            });
+
+            if let Err(e) = client.read(puzzle) {
+                eprintln!("failed to read {puzzle}: {e}");
+                return false;
+            };
+        }
+        Backend::AocCli => {
+            if aoc_cli::check().is_err() {
+                eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+                process::exit(1);
+            }
+
+            if let Err(e) = aoc_cli::read(puzzle) {
+                eprintln!("failed to call aoc-cli: {e}");
+                return false;
+            };
+        }
     }
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+    record_answers(puzzle);
+
+    true
 }
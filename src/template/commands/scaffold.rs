@@ -1,10 +1,10 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
-    process,
+    path::Path,
 };
 
-use crate::template::Day;
+use crate::template::{PuzzleId, Year};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
@@ -20,6 +20,9 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
+    if let Some(dir) = Path::new(path).parent() {
+        fs::create_dir_all(dir)?;
+    }
     OpenOptions::new()
         .write(true)
         .create(true)
@@ -27,22 +30,23 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Create the files of a day, returns whether all of them were created.
+pub fn handle(puzzle: PuzzleId, overwrite: bool) -> bool {
+    let input_path = puzzle.data_path("inputs", "txt");
+    let example_path = puzzle.data_path("examples", "txt");
+    let module_path = format!("src/bin/{}.rs", puzzle.bin_name());
 
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
         Err(e) => {
             eprintln!("Failed to create module file: {e}");
-            process::exit(1);
+            return false;
         }
     };
 
     match file.write_all(
         MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
+            .replace("%DAY_NUMBER%", &puzzle.day.into_inner().to_string())
             .as_bytes(),
     ) {
         Ok(()) => {
@@ -50,7 +54,7 @@ pub fn handle(day: Day, overwrite: bool) {
         }
         Err(e) => {
             eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
+            return false;
         }
     }
 
@@ -60,7 +64,7 @@ pub fn handle(day: Day, overwrite: bool) {
         }
         Err(e) => {
             eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+            return false;
         }
     }
 
@@ -70,10 +74,19 @@ pub fn handle(day: Day, overwrite: bool) {
         }
         Err(e) => {
             eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+            return false;
         }
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    if Year::from_env() == Some(puzzle.year) {
+        println!("🎄 Type `cargo solve {}` to run your solution.", puzzle.day);
+    } else {
+        println!(
+            "🎄 Type `cargo solve {} --year {}` to run your solution.",
+            puzzle.day, puzzle.year
+        );
+    }
+
+    true
 }
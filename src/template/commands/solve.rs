@@ -1,9 +1,22 @@
-use std::process::{Command, Stdio};
+use std::{
+    io::{BufRead, BufReader},
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
 
-use crate::template::Day;
+use crate::template::limits::{self, Limits, OUT_OF_MEMORY_MESSAGE};
+use crate::template::PuzzleId;
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+/// Run the solution of a day, returns whether it finished successfully.
+pub fn handle(
+    puzzle: PuzzleId,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    limits: Limits,
+) -> bool {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.bin_name()];
 
     if dhat {
         cmd_args.extend([
@@ -16,6 +29,20 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push("--release".to_string());
     }
 
+    // with a timeout, build the bin first so compilation does not count towards it.
+    if limits.timeout.is_some() {
+        let mut build_args = cmd_args.clone();
+        build_args[0] = "build".to_string();
+        let is_built = Command::new("cargo")
+            .args(&build_args)
+            .status()
+            .is_ok_and(|status| status.success());
+        if !is_built {
+            eprintln!("✖ Failed to build {}.", puzzle.bin_name());
+            return false;
+        }
+    }
+
     cmd_args.push("--".to_string());
 
     if let Some(submit_part) = submit_part {
@@ -23,12 +50,62 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
-    let mut cmd = Command::new("cargo")
-        .args(&cmd_args)
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .spawn()
-        .unwrap();
+    if let Some(memory_limit) = limits.memory_limit {
+        cmd_args.push("--memory-limit".to_string());
+        cmd_args.push(memory_limit.to_string());
+    }
+
+    let mut cmd = Command::new("cargo");
+    cmd.args(&cmd_args).stdout(Stdio::inherit());
+
+    // with a memory limit, stderr is forwarded line by line to detect an aborted allocation.
+    if limits.memory_limit.is_some() {
+        cmd.stderr(Stdio::piped());
+    } else {
+        cmd.stderr(Stdio::inherit());
+    }
+
+    // only a child that can time out is moved to its own process group, others should still receive Ctrl+C.
+    if limits.timeout.is_some() {
+        limits::isolate(&mut cmd);
+    }
+
+    let mut cmd = cmd.spawn().unwrap();
+
+    let stderr = cmd.stderr.take().map(|stderr| {
+        thread::spawn(move || {
+            let mut is_out_of_memory = false;
+            for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                is_out_of_memory |= line.starts_with(OUT_OF_MEMORY_MESSAGE);
+                eprintln!("{line}");
+            }
+            is_out_of_memory
+        })
+    });
+
+    let started = Instant::now();
 
-    cmd.wait().unwrap();
+    let status = loop {
+        if let Some(status) = cmd.try_wait().unwrap() {
+            break status;
+        }
+        if let Some(timeout) = limits.timeout.filter(|t| started.elapsed() >= *t) {
+            limits::kill(&mut cmd);
+            eprintln!("✖ Timed out after {timeout:.1?}.");
+            return false;
+        }
+        thread::sleep(Duration::from_millis(10));
+    };
+
+    let is_out_of_memory = stderr.is_some_and(|thread| thread.join().unwrap_or(false));
+
+    if status.success() {
+        true
+    } else if is_out_of_memory {
+        eprintln!("✖ Out of memory.");
+        false
+    } else {
+        eprintln!("✖ Failed ({status}).");
+        false
+    }
 }
@@ -0,0 +1,104 @@
+use std::{fs, path::Path};
+
+use crate::template::answers::Answers;
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{
+    PuzzleId, Year, ANSI_BOLD, ANSI_GREEN, ANSI_ITALIC, ANSI_RED, ANSI_RESET, ANSI_YELLOW,
+};
+
+/// Progress of a single part.
+enum PartProgress {
+    /// The answer was accepted by the website.
+    Star,
+    /// The solution returns an answer, but it was not accepted yet.
+    Answered,
+    /// The solution returns `None` or did not finish.
+    Unsolved,
+    /// The part has not been run yet.
+    Unknown,
+}
+
+impl PartProgress {
+    fn of(part: u8, answers: &Answers, timing: Option<&Timing>) -> Self {
+        if answers.get(part).is_some() {
+            return PartProgress::Star;
+        }
+
+        let Some(timing) = timing else {
+            return PartProgress::Unknown;
+        };
+
+        let (duration, status) = match part {
+            1 => (&timing.part_1, timing.part_1_status),
+            _ => (&timing.part_2, timing.part_2_status),
+        };
+
+        match (duration, status) {
+            (Some(_), None) => PartProgress::Answered,
+            _ => PartProgress::Unsolved,
+        }
+    }
+
+    fn format(&self) -> String {
+        match self {
+            PartProgress::Star => format!("{ANSI_YELLOW}★{ANSI_RESET}"),
+            PartProgress::Answered => format!("{ANSI_GREEN}✔{ANSI_RESET}"),
+            PartProgress::Unsolved => format!("{ANSI_RED}✖{ANSI_RESET}"),
+            PartProgress::Unknown => "·".into(),
+        }
+    }
+}
+
+pub fn handle(year: Year) {
+    let timings = Timings::read_from_file(year);
+    let mut stars = 0;
+
+    println!("{ANSI_BOLD}Day  Solution  Input  Part 1  Part 2  Time{ANSI_RESET}");
+
+    for day in year.days() {
+        let puzzle = PuzzleId::new(year, day);
+        let timing = timings.data.iter().find(|t| t.day == day);
+
+        let answers = Answers::accepted(puzzle);
+
+        let parts = [1, 2].map(|part| PartProgress::of(part, &answers, timing));
+        stars += parts
+            .iter()
+            .filter(|p| matches!(p, PartProgress::Star))
+            .count();
+
+        let has_solution = Path::new(&get_path_for_bin(puzzle)).exists();
+        let has_input = fs::read_to_string(puzzle.data_path("inputs", "txt"))
+            .is_ok_and(|input| !input.trim().is_empty());
+
+        let time = timing
+            .filter(|t| t.total_nanos > 0_f64)
+            .map_or(String::new(), |t| {
+                format!("{:.2}ms", t.total_nanos / 1_000_000_f64)
+            });
+
+        println!(
+            "{}   {}         {}      {}       {}       {ANSI_ITALIC}{time}{ANSI_RESET}",
+            day,
+            check(has_solution),
+            check(has_input),
+            parts[0].format(),
+            parts[1].format(),
+        );
+    }
+
+    println!(
+        "\n{ANSI_BOLD}Stars:{ANSI_RESET} {ANSI_YELLOW}{stars}{ANSI_RESET}/{}",
+        usize::from(year.day_count()) * 2
+    );
+    println!("{ANSI_ITALIC}★ accepted  ✔ answered  ✖ unsolved  · not run{ANSI_RESET}");
+}
+
+fn check(value: bool) -> String {
+    if value {
+        format!("{ANSI_GREEN}✔{ANSI_RESET}")
+    } else {
+        "·".into()
+    }
+}
@@ -1,34 +1,152 @@
-use std::collections::HashSet;
+use std::process;
 
-use crate::template::run_multi::run_multi;
+use crate::template::budgets::{Budgets, Violation};
+use crate::template::history::{self, Run};
+use crate::template::limits::Limits;
+use crate::template::run_multi::{run_multi, Executor};
+use crate::template::runner::{RunOptions, Solution};
+use crate::template::stats::format_nanos;
+pub use crate::template::timings::MergePolicy;
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{
+    readme_benchmarks, Day, Year, ANSI_BOLD, ANSI_GREEN, ANSI_ITALIC, ANSI_RED, ANSI_RESET,
+};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+/// Compare a run with a previous run of the benchmark history, see `cargo time --compare`.
+#[derive(Clone, Debug, Default)]
+pub struct Comparison {
+    /// Name of the baseline to compare against. Compares against the latest run if not set.
+    pub baseline: Option<String>,
+    /// Relative slowdown in percent above which a step counts as a regression.
+    pub threshold: f64,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    solutions: &[Solution],
+    year: Year,
+    days: Option<Vec<Day>>,
+    run_all: bool,
+    store: bool,
+    is_isolated: bool,
+    warmup: Option<u32>,
+    jobs: usize,
+    limits: Limits,
+    compare: Option<Comparison>,
+    save_baseline: Option<String>,
+    merge_policy: MergePolicy,
+) {
+    let stored_timings = Timings::read_from_file(year);
+
+    let budgets = Budgets::read(year).unwrap_or_else(|e| {
+        eprintln!("Could not read budgets: {e}");
+        process::exit(1);
+    });
 
-    let days_to_run = day.map_or_else(
+    // read the history before benching, so a missing baseline fails fast.
+    let reference = compare.as_ref().map(|compare| {
+        let runs = history::read(year).unwrap_or_else(|e| {
+            eprintln!("Could not read benchmark history: {e}");
+            process::exit(1);
+        });
+
+        match history::find_reference(&runs, compare.baseline.as_deref()) {
+            Some(run) => run.clone(),
+            None => {
+                match &compare.baseline {
+                    Some(name) => eprintln!("No baseline named \"{name}\" found."),
+                    None => eprintln!("No previous runs found, store one with `--store` first."),
+                }
+                process::exit(1);
+            }
+        }
+    });
+
+    let days_to_run = days.map_or_else(
         || {
-            if run_all {
-                all_days().collect()
+            // comparisons need fresh timings of every day.
+            if run_all || compare.is_some() {
+                year.days().collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
+                year.days()
                     .filter(|day| !stored_timings.is_day_complete(*day))
                     .collect()
             }
         },
-        |day| HashSet::from([day]),
+        |days| days.into_iter().collect(),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    // limits can only be enforced for solutions that run in a child process.
+    let executor = Executor::select(solutions, year, is_isolated || limits.is_set(), true);
+
+    let options = RunOptions {
+        is_timed: true,
+        warmup,
+        limits,
+        ..RunOptions::default()
+    };
+
+    let timings = run_multi(year, &days_to_run, executor, options, jobs).unwrap();
+
+    let regressions = match (compare, reference) {
+        (Some(compare), Some(reference)) => {
+            print_comparison(&reference, &timings, compare.threshold)
+        }
+        _ => 0,
+    };
+
+    let is_over_budget = budgets.is_some_and(|budgets| {
+        // days that were not run count towards the total with their stored time.
+        let year_total = timings.data.iter().map(|t| t.total_nanos).sum::<f64>()
+            + stored_timings
+                .data
+                .iter()
+                .filter(|t| !timings.data.iter().any(|n| n.day == t.day))
+                .map(|t| t.total_nanos)
+                .sum::<f64>();
+
+        print_budget_violations(&budgets.check(&timings, year_total))
+    });
+
+    // the rolling median is taken over previous runs, so read them before this run is added.
+    let previous_runs: Vec<Timings> = match (store, merge_policy) {
+        (true, MergePolicy::Median(_)) => history::read(year)
+            .unwrap_or_else(|e| {
+                eprintln!("Could not read benchmark history: {e}");
+                process::exit(1);
+            })
+            .into_iter()
+            .map(|run| run.timings)
+            .collect(),
+        _ => vec![],
+    };
+
+    if store || save_baseline.is_some() {
+        match history::append(year, &Run::new(timings.clone(), save_baseline)) {
+            Ok(()) => println!("Added run to benchmark history."),
+            Err(e) => eprintln!("Failed to add run to benchmark history: {e}"),
+        }
+    }
 
     if store {
-        let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        let merged_timings = stored_timings.merge_with(&timings, merge_policy, &previous_runs);
+        merged_timings.store_file(year).unwrap();
+
+        // the readme lists the stored benchmarks of every year that has solutions.
+        let mut years: Vec<Year> = solutions.iter().map(|s| s.puzzle.year).collect();
+        years.push(year);
+        years.sort_unstable();
+        years.dedup();
+
+        let tables: Vec<(Year, Timings)> = years
+            .into_iter()
+            .map(|y| (y, Timings::read_from_file(y)))
+            .filter(|(_, timings)| !timings.data.is_empty())
+            .collect();
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(&tables) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
@@ -37,4 +155,75 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             }
         }
     }
+
+    if is_over_budget || regressions > 0 {
+        process::exit(1);
+    }
+}
+
+/// Print the budgets that were exceeded, returns whether there were any.
+fn print_budget_violations(violations: &[Violation]) -> bool {
+    if violations.is_empty() {
+        println!("\n{ANSI_GREEN}All budgets met.{ANSI_RESET}");
+        return false;
+    }
+
+    println!(
+        "\n{ANSI_BOLD}{ANSI_RED}{} budget(s) exceeded:{ANSI_RESET}",
+        violations.len()
+    );
+    for violation in violations {
+        println!("{}", violation.describe());
+    }
+
+    true
+}
+
+/// Print how every step changed compared to `reference`, returns the number of regressions.
+fn print_comparison(reference: &Run, timings: &Timings, threshold: f64) -> usize {
+    let changes = history::compare(&reference.timings, timings);
+
+    println!(
+        "\n{ANSI_BOLD}Compared to {}:{ANSI_RESET}",
+        reference.describe()
+    );
+
+    if changes.is_empty() {
+        println!("{ANSI_ITALIC}No steps were benched in both runs.{ANSI_RESET}");
+        return 0;
+    }
+
+    for change in &changes {
+        let percent = change.percent();
+        let (color, note) = if change.is_regression(threshold) {
+            (ANSI_RED, "  regressed")
+        } else if percent < -threshold {
+            (ANSI_GREEN, "")
+        } else {
+            ("", "")
+        };
+
+        println!(
+            "Day {}  {:<6}  {} → {}  {color}{percent:+.1}%{note}{ANSI_RESET}",
+            change.day,
+            change.step,
+            format_nanos(change.before),
+            format_nanos(change.after),
+        );
+    }
+
+    let regressions = changes
+        .iter()
+        .filter(|c| c.is_regression(threshold))
+        .count();
+
+    if regressions > 0 {
+        println!(
+            "\n{ANSI_RED}{regressions} step(s) regressed by more than {threshold}%.{ANSI_RESET}"
+        );
+    } else {
+        println!("\n{ANSI_GREEN}No regressions above {threshold}%.{ANSI_RESET}");
+    }
+
+    regressions
 }
@@ -0,0 +1,143 @@
+use std::{path::Path, process};
+
+use crate::template::answers::Answers;
+use crate::template::run_multi::{get_path_for_bin, Executor};
+use crate::template::runner::{RunOptions, Solution};
+use crate::template::{
+    Answer, Day, PuzzleId, Year, ANSI_BOLD, ANSI_GREEN, ANSI_RED, ANSI_RESET, ANSI_YELLOW,
+};
+
+/// Outcome of comparing the answer of a part with its accepted answer.
+#[derive(Debug, PartialEq, Eq)]
+enum Verdict {
+    Pass,
+    Fail,
+    /// The part has no accepted answer.
+    Missing,
+}
+
+impl Verdict {
+    /// Answers drawn on a grid are compared by the letters they show, as they were submitted.
+    fn of(answer: Option<&str>, expected: Option<&str>) -> Self {
+        match (answer, expected) {
+            (Some(answer), Some(expected)) if Answer::from(answer).matches(expected) => {
+                Verdict::Pass
+            }
+            (_, Some(_)) => Verdict::Fail,
+            (_, None) => Verdict::Missing,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct Summary {
+    passed: usize,
+    failed: usize,
+    missing: usize,
+    /// Days whose accepted answers could not be read.
+    errors: usize,
+}
+
+pub fn handle(solutions: &[Solution], year: Year, days: Option<Vec<Day>>) {
+    let mut summary = Summary::default();
+
+    let executor = Executor::select(solutions, year, false, true);
+
+    year.days()
+        .filter(|d| days.as_ref().is_none_or(|days| days.contains(d)))
+        .map(|d| PuzzleId::new(year, d))
+        .filter(|puzzle| Path::new(&get_path_for_bin(*puzzle)).exists())
+        .for_each(|puzzle| {
+            let day = puzzle.day;
+            let answers = match Answers::read(puzzle) {
+                Ok(answers) => answers,
+                Err(e) => {
+                    eprintln!("Day {day}: could not read answers: {e}");
+                    summary.errors += 1;
+                    return;
+                }
+            };
+
+            let result = executor.run_quiet(puzzle, RunOptions::default());
+
+            for part in 1..=2 {
+                let answer = result
+                    .parts
+                    .iter()
+                    .find(|r| r.part == part)
+                    .and_then(|r| r.answer.as_deref());
+                let expected = answers.get(part);
+
+                // grids are shown as the letters drawn on them.
+                let shown = answer.map(|a| {
+                    Answer::from(a)
+                        .to_submission()
+                        .unwrap_or_else(|| a.to_string())
+                });
+
+                let status = match (Verdict::of(answer, expected), shown, expected) {
+                    (Verdict::Pass, _, Some(expected)) => {
+                        summary.passed += 1;
+                        format!("{ANSI_GREEN}pass{ANSI_RESET}     {expected}")
+                    }
+                    (Verdict::Fail, Some(answer), Some(expected)) => {
+                        summary.failed += 1;
+                        format!("{ANSI_RED}FAIL{ANSI_RESET}     got {answer}, expected {expected}")
+                    }
+                    (_, None, Some(expected)) => {
+                        summary.failed += 1;
+                        format!("{ANSI_RED}FAIL{ANSI_RESET}     no result, expected {expected}")
+                    }
+                    (_, Some(answer), _) => {
+                        summary.missing += 1;
+                        format!("{ANSI_YELLOW}missing{ANSI_RESET}  {answer} (no accepted answer)")
+                    }
+                    (_, None, None) => {
+                        summary.missing += 1;
+                        format!("{ANSI_YELLOW}missing{ANSI_RESET}  not solved")
+                    }
+                };
+
+                println!("{ANSI_BOLD}Day {day}{ANSI_RESET}  Part {part}  {status}");
+            }
+        });
+
+    println!(
+        "\n{ANSI_BOLD}Verified:{ANSI_RESET} {} passed, {} failed, {} missing",
+        summary.passed, summary.failed, summary.missing
+    );
+
+    if summary.errors > 0 {
+        eprintln!("Could not read the answers of {} day(s).", summary.errors);
+    }
+
+    if summary.failed > 0 || summary.errors > 0 {
+        process::exit(1);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Verdict;
+
+    #[test]
+    fn compares_grid_answers_by_their_letters() {
+        let grid = [
+            "#..#.###.",
+            "#..#..#..",
+            "####..#..",
+            "#..#..#..",
+            "#..#..#..",
+            "#..#.###.",
+        ]
+        .join("\n");
+
+        assert_eq!(Verdict::of(Some(&grid), Some("HI")), Verdict::Pass);
+        assert_eq!(Verdict::of(Some(&grid), Some("HJ")), Verdict::Fail);
+        assert_eq!(Verdict::of(Some("42"), Some("42")), Verdict::Pass);
+        assert_eq!(Verdict::of(None, Some("42")), Verdict::Fail);
+        assert_eq!(Verdict::of(Some(&grid), None), Verdict::Missing);
+    }
+}
@@ -0,0 +1,137 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::PathBuf,
+    process::Command,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::run_multi::child_commands;
+use crate::template::runner::{
+    format_parse_result, format_part_result, DayResult, OutputFormat, RunOptions,
+};
+use crate::template::{data_dir, PuzzleId, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Interval in which the watched files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Last modification time of every watched file.
+type Snapshot = BTreeMap<PathBuf, Option<SystemTime>>;
+
+pub fn handle(puzzle: PuzzleId, is_release: bool) {
+    let bin_name = puzzle.bin_name();
+    let mut snapshot = Snapshot::new();
+    let mut previous: Option<DayResult> = None;
+
+    println!("Watching {puzzle} for changes, press Ctrl+C to stop.");
+
+    loop {
+        let current = take_snapshot(puzzle);
+
+        if current != snapshot {
+            snapshot = current;
+            println!("\n{ANSI_BOLD}Day {}{ANSI_RESET}", puzzle.day);
+            println!("------");
+
+            if run_cargo(&["build", "--quiet", "--bin", &bin_name], is_release) {
+                if run_cargo(&["test", "--quiet", "--bin", &bin_name], is_release) {
+                    println!("Example tests passed.");
+                }
+
+                let options = RunOptions {
+                    format: OutputFormat::Quiet,
+                    ..RunOptions::default()
+                };
+
+                match child_commands::run_solution(puzzle, options, is_release, None) {
+                    Ok(result) => {
+                        print_result(&result, previous.as_ref());
+                        previous = Some(result);
+                    }
+                    Err(e) => eprintln!("Failed to run {bin_name}: {e:?}"),
+                }
+            }
+
+            println!("\n{ANSI_ITALIC}Waiting for changes...{ANSI_RESET}");
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Files that affect the result of a day: its solution, the library, its examples and its input.
+fn take_snapshot(puzzle: PuzzleId) -> Snapshot {
+    let mut paths = vec![
+        PathBuf::from(format!("src/bin/{}.rs", puzzle.bin_name())),
+        PathBuf::from("src/lib.rs"),
+        PathBuf::from(puzzle.data_path("inputs", "txt")),
+    ];
+
+    // e.g. `01.txt`, `01-2.txt` and the examples manifest `01.json`.
+    let examples_dir = PathBuf::from(format!("{}/examples", data_dir(puzzle.year)));
+    if let Ok(entries) = fs::read_dir(&examples_dir) {
+        let prefix = puzzle.day.to_string();
+        paths.extend(
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.file_name()
+                        .and_then(|name| name.to_str())
+                        .is_some_and(|name| name.starts_with(&prefix))
+                }),
+        );
+    }
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let modified = fs::metadata(&path).and_then(|m| m.modified()).ok();
+            (path, modified)
+        })
+        .collect()
+}
+
+/// Run a cargo command with inherited output, returns whether it succeeded.
+fn run_cargo(args: &[&str], is_release: bool) -> bool {
+    let mut cmd = Command::new("cargo");
+    cmd.args(args);
+    if is_release {
+        cmd.arg("--release");
+    }
+    cmd.status().is_ok_and(|status| status.success())
+}
+
+/// Print the results of the real input, and how the answers changed since the previous run.
+fn print_result(result: &DayResult, previous: Option<&DayResult>) {
+    if let Some(parse) = &result.parse {
+        println!("{}", format_parse_result(parse));
+    }
+
+    if result.parts.is_empty() {
+        println!("Not solved.");
+        return;
+    }
+
+    for part in &result.parts {
+        let before = previous
+            .and_then(|p| p.parts.iter().find(|r| r.part == part.part))
+            .map(|r| r.answer.as_deref().unwrap_or("✖"));
+        let after = part.answer.as_deref().unwrap_or("✖");
+
+        let change = match before {
+            Some(before) if before == after => "unchanged".to_string(),
+            Some(before) if !before.contains('\n') && !after.contains('\n') => {
+                format!("was {before}")
+            }
+            Some(_) => "changed".to_string(),
+            None => "new".to_string(),
+        };
+
+        println!(
+            "{} {ANSI_ITALIC}({change}){ANSI_RESET}",
+            format_part_result(part)
+        );
+    }
+}
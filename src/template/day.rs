@@ -5,10 +5,14 @@ use std::str::FromStr;
 #[cfg(feature = "today")]
 use chrono::{Datelike, FixedOffset, Utc};
 
+#[cfg(feature = "today")]
+use crate::template::Year;
+
 #[cfg(feature = "today")]
 const SERVER_UTC_OFFSET: i32 = -5;
 
 /// A valid day number of advent (i.e. an integer in range 1 to 25).
+/// Some years have fewer puzzles, see [`Year::day_count`](crate::template::Year::day_count).
 ///
 /// # Display
 /// This value displays as a two digit number.
@@ -38,19 +42,21 @@ impl Day {
     }
 
     /// Converts the [`Day`] into an [`u8`].
-    pub fn into_inner(self) -> u8 {
+    pub const fn into_inner(self) -> u8 {
         self.0
     }
 }
 
 #[cfg(feature = "today")]
 impl Day {
-    /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
+    /// Returns the current day if it's a puzzle day of the current year's advent, `None` otherwise.
     pub fn today() -> Option<Self> {
         let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
         let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
-            Self::new(u8::try_from(today.day()).ok()?)
+        let year = Year::new(u16::try_from(today.year()).ok()?)?;
+        let day = Self::new(u8::try_from(today.day()).ok()?)?;
+        if today.month() == 12 && year.contains(day) {
+            Some(day)
         } else {
             None
         }
@@ -101,19 +107,29 @@ impl Display for DayFromStrError {
 /* -------------------------------------------------------------------------- */
 
 /// An iterator that yields every day of advent from the 1st to the 25th.
+/// Use [`Year::days`](crate::template::Year::days) to only yield the puzzle days of a year.
 pub fn all_days() -> AllDays {
     AllDays::new()
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of advent from the 1st to the last day, 25th by default.
 pub struct AllDays {
     current: u8,
+    last: u8,
 }
 
 impl AllDays {
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
-        Self { current: 1 }
+        Self::until(25)
+    }
+
+    /// Creates an iterator that stops after the given day, capped at the 25th.
+    pub fn until(last: u8) -> Self {
+        Self {
+            current: 1,
+            last: last.min(25),
+        }
     }
 }
 
@@ -121,7 +137,7 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.last {
             return None;
         }
         // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
@@ -187,6 +203,14 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn all_days_iterator_until() {
+        let days: Vec<Day> = super::AllDays::until(12).collect();
+        assert_eq!(days.len(), 12);
+        assert_eq!(days.last(), Some(&Day(12)));
+        assert_eq!(super::AllDays::until(30).count(), 25);
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -0,0 +1,154 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::template::answers::Answers;
+use crate::template::{Day, PuzzleId, Year};
+
+/// A selection of days as passed on the command-line.
+///
+/// Accepts a single day (`5`), a range (`1..=5` or `1..6`), a list of both (`3,7,11` or `1..=3,7`),
+/// `all` days of the year, or the days that are `unsolved`, i.e. do not have accepted answers for both parts.
+///
+/// ```
+/// # use advent_of_code::template::{Day, DaySelection};
+/// let selection: DaySelection = "1..=3,7".parse().unwrap();
+/// assert_eq!(selection, DaySelection::Days(vec![1, 2, 3, 7].into_iter().map(|d| Day::new(d).unwrap()).collect()));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DaySelection {
+    All,
+    Unsolved,
+    Days(Vec<Day>),
+}
+
+impl DaySelection {
+    /// The selected days of a year in ascending order, without duplicates.
+    /// Days of a list that the year does not have are kept, so callers can reject them.
+    pub fn days(&self, year: Year) -> Vec<Day> {
+        match self {
+            DaySelection::All => year.days().collect(),
+            DaySelection::Unsolved => year
+                .days()
+                .filter(|day| !Answers::accepted(PuzzleId::new(year, *day)).is_complete())
+                .collect(),
+            DaySelection::Days(days) => {
+                let mut days = days.clone();
+                days.sort_unstable();
+                days.dedup();
+                days
+            }
+        }
+    }
+}
+
+impl FromStr for DaySelection {
+    type Err = DaySelectionFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "all" => return Ok(DaySelection::All),
+            "unsolved" => return Ok(DaySelection::Unsolved),
+            _ => {}
+        }
+
+        let mut days = vec![];
+
+        for item in s.split(',') {
+            let range = match (item.split_once("..="), item.split_once("..")) {
+                (Some((start, end)), _) => Some((start, parse_day(end)?)),
+                (None, Some((start, end))) => {
+                    // an exclusive range may end after the last day, e.g. `20..26`.
+                    let end = end.parse::<u8>().map_err(|_| DaySelectionFromStrError)?;
+                    let end = end.checked_sub(1).and_then(Day::new);
+                    Some((start, end.ok_or(DaySelectionFromStrError)?))
+                }
+                (None, None) => None,
+            };
+
+            match range {
+                Some((start, end)) => {
+                    let start = parse_day(start)?;
+                    if start > end {
+                        return Err(DaySelectionFromStrError);
+                    }
+                    days.extend((start.into_inner()..=end.into_inner()).filter_map(Day::new));
+                }
+                None => days.push(parse_day(item)?),
+            }
+        }
+
+        Ok(DaySelection::Days(days))
+    }
+}
+
+fn parse_day(s: &str) -> Result<Day, DaySelectionFromStrError> {
+    s.trim().parse().map_err(|_| DaySelectionFromStrError)
+}
+
+/// An error which can be returned when parsing a [`DaySelection`].
+#[derive(Debug)]
+pub struct DaySelectionFromStrError;
+
+impl Error for DaySelectionFromStrError {}
+
+impl Display for DaySelectionFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(
+            "expecting a day, a range like `1..=5`, a list like `3,7,11`, `all` or `unsolved`",
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::DaySelection;
+    use crate::{day, year};
+
+    fn days(s: &str) -> Vec<u8> {
+        s.parse::<DaySelection>()
+            .unwrap()
+            .days(year!(2020))
+            .into_iter()
+            .map(|d| d.into_inner())
+            .collect()
+    }
+
+    #[test]
+    fn parses_single_days_and_lists() {
+        assert_eq!(days("5"), vec![5]);
+        assert_eq!(days("11,3,7,3"), vec![3, 7, 11]);
+        assert_eq!(
+            "05".parse::<DaySelection>().unwrap(),
+            DaySelection::Days(vec![day!(5)])
+        );
+    }
+
+    #[test]
+    fn parses_ranges() {
+        assert_eq!(days("1..=5"), vec![1, 2, 3, 4, 5]);
+        assert_eq!(days("23..26"), vec![23, 24, 25]);
+        assert_eq!(days("1..=2,10,24..=25"), vec![1, 2, 10, 24, 25]);
+    }
+
+    #[test]
+    fn parses_keywords() {
+        assert_eq!(days("all").len(), 25);
+        assert_eq!(DaySelection::All.days(year!(2025)).len(), 12);
+        assert_eq!(
+            "unsolved".parse::<DaySelection>().unwrap(),
+            DaySelection::Unsolved
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_selections() {
+        for s in [
+            "", "0", "26", "5..=3", "1..=26", "1..1", "a", "1,,2", "1...3",
+        ] {
+            assert!(s.parse::<DaySelection>().is_err(), "{s}");
+        }
+    }
+}
@@ -0,0 +1,217 @@
+//! The machine and build that benchmarks were measured with.
+use std::{collections::HashMap, fmt::Display, fs, process::Command, thread};
+use tinyjson::JsonValue;
+
+/// Describes the machine and build of a benchmark run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Environment {
+    /// CPU model name, if it could be detected.
+    pub cpu: Option<String>,
+    pub cores: usize,
+    /// Operating system and architecture, e.g. `linux x86_64`.
+    pub os: String,
+    /// Output of `rustc --version` of the toolchain that built the solutions.
+    pub rustc: String,
+    /// Cargo profile of the build, e.g. `release`.
+    pub profile: String,
+    /// Enabled cargo features of the build.
+    pub features: Vec<String>,
+}
+
+impl Environment {
+    /// Describe the machine the process runs on and the build it was compiled with.
+    pub fn detect() -> Self {
+        Environment {
+            cpu: cpu_model(),
+            cores: thread::available_parallelism().map_or(1, |n| n.get()),
+            os: format!("{} {}", std::env::consts::OS, std::env::consts::ARCH),
+            rustc: env!("AOC_BUILD_RUSTC").into(),
+            profile: env!("AOC_BUILD_PROFILE").into(),
+            features: env!("AOC_BUILD_FEATURES")
+                .split(',')
+                .filter(|f| !f.is_empty())
+                .map(Into::into)
+                .collect(),
+        }
+    }
+
+    /// Names of the properties that differ between two environments.
+    pub fn differences(&self, other: &Self) -> Vec<&'static str> {
+        [
+            ("cpu", self.cpu != other.cpu),
+            ("cores", self.cores != other.cores),
+            ("os", self.os != other.os),
+            ("rustc", self.rustc != other.rustc),
+            ("profile", self.profile != other.profile),
+            ("features", self.features != other.features),
+        ]
+        .into_iter()
+        .filter_map(|(name, differs)| differs.then_some(name))
+        .collect()
+    }
+}
+
+impl Display for Environment {
+    /// e.g. `AMD Ryzen 7 5800X (16 cores), linux x86_64, rustc 1.85.0, release profile, no features`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let cpu = self.cpu.as_deref().unwrap_or("unknown CPU");
+        let features = if self.features.is_empty() {
+            "no features".to_string()
+        } else {
+            format!("features: {}", self.features.join(", "))
+        };
+
+        write!(
+            f,
+            "{cpu} ({} cores), {}, {}, {} profile, {features}",
+            self.cores, self.os, self.rustc, self.profile
+        )
+    }
+}
+
+fn cpu_model() -> Option<String> {
+    if cfg!(target_os = "macos") {
+        let output = Command::new("sysctl")
+            .args(["-n", "machdep.cpu.brand_string"])
+            .output()
+            .ok()?;
+        let model = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        return (!model.is_empty()).then_some(model);
+    }
+
+    let cpuinfo = fs::read_to_string("/proc/cpuinfo").ok()?;
+    cpuinfo
+        .lines()
+        .find_map(|line| line.strip_prefix("model name")?.split_once(':'))
+        .map(|(_, model)| model.trim().to_string())
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Environment> for JsonValue {
+    fn from(value: &Environment) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "cpu".into(),
+            value.cpu.clone().map_or(JsonValue::Null, JsonValue::String),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("cores".into(), JsonValue::Number(value.cores as f64));
+        map.insert("os".into(), JsonValue::String(value.os.clone()));
+        map.insert("rustc".into(), JsonValue::String(value.rustc.clone()));
+        map.insert("profile".into(), JsonValue::String(value.profile.clone()));
+        map.insert(
+            "features".into(),
+            JsonValue::Array(
+                value
+                    .features
+                    .iter()
+                    .map(|f| JsonValue::String(f.clone()))
+                    .collect(),
+            ),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Environment {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected environment to be a JSON object.")?;
+
+        let string = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>())
+                .cloned()
+                .ok_or(format!("Expected environment.{key} to be a string."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let cores = json
+            .get("cores")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as usize)
+            .ok_or("Expected environment.cores to be a number.")?;
+
+        let features = json
+            .get("features")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected environment.features to be an array.")?
+            .iter()
+            .map(|v| v.get::<String>().cloned())
+            .collect::<Option<_>>()
+            .ok_or("Expected environment.features to contain strings.")?;
+
+        Ok(Environment {
+            cpu: json.get("cpu").and_then(|v| v.get::<String>()).cloned(),
+            cores,
+            os: string("os")?,
+            rustc: string("rustc")?,
+            profile: string("profile")?,
+            features,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::Environment;
+
+    fn get_mock_environment() -> Environment {
+        Environment {
+            cpu: Some("AMD Ryzen 7 5800X".into()),
+            cores: 16,
+            os: "linux x86_64".into(),
+            rustc: "rustc 1.85.0".into(),
+            profile: "release".into(),
+            features: vec![],
+        }
+    }
+
+    #[test]
+    fn roundtrips_environments() {
+        let environment = Environment {
+            features: vec!["dhat-heap".into()],
+            ..get_mock_environment()
+        };
+        let json = JsonValue::from(&environment);
+        assert_eq!(Environment::try_from(&json).unwrap(), environment);
+    }
+
+    #[test]
+    fn detects_the_build() {
+        let environment = Environment::detect();
+        assert!(environment.rustc.starts_with("rustc"));
+        assert!(environment.features.contains(&"test-lib".to_string()));
+        assert!(environment.cores > 0);
+    }
+
+    #[test]
+    fn lists_differences() {
+        let environment = get_mock_environment();
+        let other = Environment {
+            cores: 8,
+            profile: "debug".into(),
+            ..get_mock_environment()
+        };
+        assert!(environment.differences(&environment).is_empty());
+        assert_eq!(environment.differences(&other), vec!["cores", "profile"]);
+    }
+
+    #[test]
+    fn formats_environments() {
+        assert_eq!(
+            get_mock_environment().to_string(),
+            "AMD Ryzen 7 5800X (16 cores), linux x86_64, rustc 1.85.0, release profile, no features"
+        );
+    }
+}
@@ -0,0 +1,474 @@
+//! Examples of a puzzle and their expected answers.
+//!
+//! Puzzles with several examples list them in a manifest next to the example files, e.g. `data/2020/examples/15.json`:
+//! `{ "examples": [{ "name": "short", "input": "0,3,6", "part_1": "436" }, { "file": "15-2.txt", "part_2": "175594" }] }`.
+//! Each example either has an inline `input` or a `file` in the examples directory.
+//!
+//! The example input and its expected answers are also extracted from downloaded puzzle descriptions.
+//! Puzzles show the example in the first code block and highlight answers as emphasized code, e.g. `` `*514579*` ``.
+
+use std::{collections::HashMap, fs, io, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{data_dir, PartOutput, PuzzleId};
+
+static EXAMPLES_DIR: &str = "examples";
+
+/// A named example input with the expected answers of its parts.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Example {
+    pub name: String,
+    pub input: String,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl Example {
+    /// Get the expected answer of a part (1 or 2).
+    pub fn expected(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            2 => self.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Assert that the output of a part matches the expected answer, if the example has one.
+    ///
+    /// ```ignore
+    /// for example in advent_of_code::template::read_examples(PUZZLE) {
+    ///     example.assert_part(1, part_one(&example.input));
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn assert_part(&self, part: u8, output: impl PartOutput) {
+        let Some(expected) = self.expected(part) else {
+            return;
+        };
+
+        let answer = output.into_answer();
+        if let Ok(Some(answer)) = &answer {
+            if answer.matches(expected) {
+                return;
+            }
+        }
+
+        let answer = answer.map(|a| a.map(|a| a.to_string()));
+        assert_eq!(
+            answer,
+            Ok(Some(expected.to_string())),
+            "example `{}`, part {part}",
+            self.name
+        );
+    }
+}
+
+/// Read the examples of a day from its manifest.
+/// If the day has no manifest, returns its example file as a single example without expected answers.
+pub fn read(puzzle: PuzzleId) -> Result<Vec<Example>, String> {
+    match fs::read_to_string(puzzle.data_path(EXAMPLES_DIR, "json")) {
+        Ok(s) => parse_manifest(&s, |file| {
+            let path = format!("{}/{EXAMPLES_DIR}/{file}", data_dir(puzzle.year));
+            fs::read_to_string(&path).map_err(|e| format!("could not read \"{path}\": {e}"))
+        }),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            let input = fs::read_to_string(puzzle.data_path(EXAMPLES_DIR, "txt"))
+                .map_err(|e| e.to_string())?;
+            Ok(vec![Example {
+                name: puzzle.day.to_string(),
+                input,
+                ..Example::default()
+            }])
+        }
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+fn parse_manifest(
+    manifest: &str,
+    read_file: impl Fn(&str) -> Result<String, String>,
+) -> Result<Vec<Example>, String> {
+    let json = JsonValue::from_str(manifest).or(Err("not valid JSON file."))?;
+
+    let examples = json
+        .get::<HashMap<String, JsonValue>>()
+        .ok_or("expected JSON document to be an object.")?
+        .get("examples")
+        .ok_or("expected JSON document to have key `examples`.")?
+        .get::<Vec<JsonValue>>()
+        .ok_or("expected `examples` to be an array.")?;
+
+    examples
+        .iter()
+        .enumerate()
+        .map(|(i, example)| {
+            let json = example
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("expected example to be an object.")?;
+
+            let read_string = |key: &str| match json.get(key) {
+                None => Ok(None),
+                Some(v) if v.is_null() => Ok(None),
+                Some(v) => v
+                    .get::<String>()
+                    .cloned()
+                    .map(Some)
+                    .ok_or(format!("expected `{key}` to be null or string.")),
+            };
+
+            let file = read_string("file")?;
+            let input = match (read_string("input")?, &file) {
+                (Some(input), _) => input,
+                (None, Some(file)) => read_file(file)?,
+                (None, None) => return Err("expected example to have `input` or `file`.".into()),
+            };
+
+            // unnamed examples are named after their file, or their input if it is short.
+            let name = read_string("name")?.or(file).unwrap_or_else(|| {
+                if input.len() <= 20 && !input.trim_end().contains('\n') {
+                    input.trim_end().to_string()
+                } else {
+                    format!("#{}", i + 1)
+                }
+            });
+
+            Ok(Example {
+                name,
+                input,
+                part_1: read_string("part_1")?,
+                part_2: read_string("part_2")?,
+            })
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The example of a puzzle as found in its description.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PuzzleExample {
+    pub input: Option<String>,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+/// Find the example input and expected answers in the markdown of a puzzle description.
+/// The expected answer of a part is the last highlighted value in its description.
+pub fn extract(markdown: &str) -> PuzzleExample {
+    let (part_1, part_2) = match markdown.find("--- Part Two ---") {
+        Some(index) => (&markdown[..index], Some(&markdown[index..])),
+        None => (markdown, None),
+    };
+
+    PuzzleExample {
+        input: first_code_block(part_1),
+        part_1: last_highlight(part_1),
+        part_2: part_2.and_then(last_highlight),
+    }
+}
+
+fn first_code_block(markdown: &str) -> Option<String> {
+    let mut lines = markdown.lines().skip_while(|line| !line.starts_with("```"));
+    lines.next()?;
+
+    let mut block = String::new();
+    for line in lines {
+        if line.starts_with("```") {
+            return Some(block);
+        }
+        block.push_str(line);
+        block.push('\n');
+    }
+
+    None
+}
+
+fn last_highlight(markdown: &str) -> Option<String> {
+    let mut in_code_block = false;
+    let mut highlight = None;
+
+    for line in markdown.lines() {
+        if line.starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+
+        // the answer to the real input is not part of the example.
+        if in_code_block || line.starts_with("Your puzzle answer was") {
+            continue;
+        }
+
+        for value in highlighted_code(line) {
+            highlight = Some(value);
+        }
+    }
+
+    highlight
+}
+
+/// Values of code spans that are emphasized, e.g. `` `*42*` `` or `` *`42`* ``.
+fn highlighted_code(line: &str) -> Vec<String> {
+    let spans: Vec<&str> = line.split('`').collect();
+
+    // every odd element is the content of a code span, if it is closed.
+    (1..spans.len().saturating_sub(1))
+        .step_by(2)
+        .filter_map(|i| {
+            let code = spans[i];
+            let is_wrapped = spans[i - 1].ends_with('*') && spans[i + 1].starts_with('*');
+
+            // the emphasized part may also be the end of a calculation, e.g. `` `4 + 55 = *59*` ``.
+            let emphasized = code
+                .strip_suffix('*')
+                .and_then(|c| c.rfind('*').map(|start| &c[start + 1..]));
+
+            match emphasized {
+                Some(value) if !value.is_empty() => Some(value.to_string()),
+                _ if is_wrapped && !code.is_empty() => Some(code.to_string()),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Write the extracted example of a puzzle to its example file and fill the expected answers into the tests of its solution.
+/// Existing examples and expectations are kept.
+pub fn apply(puzzle: PuzzleId) -> io::Result<()> {
+    let markdown = fs::read_to_string(puzzle.data_path("puzzles", "md"))?;
+    let example = extract(&markdown);
+
+    let example_path = puzzle.data_path("examples", "txt");
+    let is_empty = fs::read_to_string(&example_path).map_or(true, |s| s.trim().is_empty());
+
+    if let (Some(input), true) = (&example.input, is_empty) {
+        fs::write(&example_path, input)?;
+        println!("🎄 Wrote example to \"{example_path}\".");
+    }
+
+    let module_path = format!("src/bin/{}.rs", puzzle.bin_name());
+    let Ok(mut module) = fs::read_to_string(&module_path) else {
+        return Ok(());
+    };
+
+    let mut is_changed = false;
+    for (test, answer) in [
+        ("test_part_one", &example.part_1),
+        ("test_part_two", &example.part_2),
+    ] {
+        if let Some(answer) = answer {
+            is_changed |= fill_expectation(&mut module, test, answer);
+        }
+    }
+
+    if is_changed {
+        fs::write(&module_path, module)?;
+        println!("🎄 Filled expected example answers into \"{module_path}\".");
+    }
+
+    Ok(())
+}
+
+/// Replace the `None` expectation of a scaffolded test with the answer.
+/// Only numeric answers are filled in, since the scaffolded parts return numbers.
+fn fill_expectation(module: &mut String, test: &str, answer: &str) -> bool {
+    const EXPECT_NONE: &str = "assert_eq!(result, None);";
+
+    if answer.parse::<i128>().is_err() {
+        return false;
+    }
+
+    let Some(start) = module.find(&format!("fn {test}()")) else {
+        return false;
+    };
+
+    // only look into the body of the test, i.e. up to the next function.
+    let end = module[start + 1..]
+        .find("fn ")
+        .map_or(module.len(), |i| start + 1 + i);
+
+    match module[start..end].find(EXPECT_NONE) {
+        Some(i) => {
+            let at = start + i;
+            module.replace_range(
+                at..at + EXPECT_NONE.len(),
+                &format!("assert_eq!(result, Some({answer}));"),
+            );
+            true
+        }
+        None => false,
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{extract, fill_expectation, parse_manifest, Example, PuzzleExample};
+    use crate::template::Answer;
+
+    const PUZZLE: &str = "\
+## --- Day 1: Report Repair ---
+
+For example, suppose your expense report contained the following:
+
+```
+1721
+979
+```
+
+In this list, the two entries that sum to `*2020*` are `*1721*` and `*299*`. Multiplying them together produces `1721 * 299 = *514579*`.
+
+Your puzzle answer was `1007104`.
+
+## --- Part Two ---
+
+In your expense report, the product of them is *`241861950`*.
+
+Your puzzle answer was `18847752`.
+";
+
+    #[test]
+    fn parses_manifests() {
+        let manifest = r#"{ "examples": [
+            { "name": "short", "input": "0,3,6", "part_1": "436", "part_2": null },
+            { "file": "15-2.txt", "part_2": "175594" },
+            { "input": "1,3,2" },
+            { "input": "1\n2\n3" }
+        ] }"#;
+
+        let examples = parse_manifest(manifest, |file| Ok(format!("content of {file}"))).unwrap();
+
+        assert_eq!(
+            examples,
+            vec![
+                Example {
+                    name: "short".into(),
+                    input: "0,3,6".into(),
+                    part_1: Some("436".into()),
+                    part_2: None,
+                },
+                Example {
+                    name: "15-2.txt".into(),
+                    input: "content of 15-2.txt".into(),
+                    part_1: None,
+                    part_2: Some("175594".into()),
+                },
+                Example {
+                    name: "1,3,2".into(),
+                    input: "1,3,2".into(),
+                    part_1: None,
+                    part_2: None,
+                },
+                Example {
+                    name: "#4".into(),
+                    input: "1\n2\n3".into(),
+                    part_1: None,
+                    part_2: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_examples_without_input() {
+        parse_manifest(r#"{ "examples": [{ "part_1": "1" }] }"#, |_| {
+            Ok(String::new())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn asserts_expected_answers() {
+        let example = Example {
+            name: "short".into(),
+            input: "0,3,6".into(),
+            part_1: Some("436".into()),
+            part_2: None,
+        };
+        example.assert_part(1, Some(436_u32));
+        example.assert_part(2, None::<u32>);
+
+        let example = Example {
+            name: "grid".into(),
+            part_1: Some("HI".into()),
+            ..Example::default()
+        };
+        example.assert_part(
+            1,
+            Some(Answer::grid([
+                "#..#.###.",
+                "#..#..#..",
+                "####..#..",
+                "#..#..#..",
+                "#..#..#..",
+                "#..#.###.",
+            ])),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "example `short`, part 1")]
+    fn panics_for_wrong_answers() {
+        let example = Example {
+            name: "short".into(),
+            input: "0,3,6".into(),
+            part_1: Some("436".into()),
+            part_2: None,
+        };
+        example.assert_part(1, Some(1_u32));
+    }
+
+    #[test]
+    fn extracts_example_and_answers() {
+        assert_eq!(
+            extract(PUZZLE),
+            PuzzleExample {
+                input: Some("1721\n979\n".into()),
+                part_1: Some("514579".into()),
+                part_2: Some("241861950".into()),
+            }
+        );
+    }
+
+    #[test]
+    fn handles_unsolved_part_one() {
+        let markdown = PUZZLE.split("\nYour puzzle answer").next().unwrap();
+        let example = extract(markdown);
+        assert_eq!(example.part_1, Some("514579".into()));
+        assert_eq!(example.part_2, None);
+        assert_eq!(extract("no example").input, None);
+    }
+
+    #[test]
+    fn fills_expectations_of_scaffolded_tests() {
+        let mut module = "\
+fn test_part_one() {
+    assert_eq!(result, None);
+}
+
+fn test_part_two() {
+    assert_eq!(result, None);
+}
+"
+        .to_string();
+
+        assert!(fill_expectation(&mut module, "test_part_two", "42"));
+        assert!(!fill_expectation(&mut module, "test_part_two", "43"));
+        assert!(!fill_expectation(&mut module, "test_part_one", "abc"));
+        assert_eq!(
+            module,
+            "\
+fn test_part_one() {
+    assert_eq!(result, None);
+}
+
+fn test_part_two() {
+    assert_eq!(result, Some(42));
+}
+"
+        );
+    }
+}
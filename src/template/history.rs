@@ -0,0 +1,397 @@
+//! Append-only log of benchmark runs, used to detect regressions between runs.
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    process::Command,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{
+    data_dir,
+    timings::{read_environment, read_version, Timing, Timings, TIMINGS_VERSION},
+    Day, Year,
+};
+
+static HISTORY_FILE_NAME: &str = "timings_history.jsonl";
+
+/// A stored benchmark run.
+#[derive(Clone, Debug)]
+pub struct Run {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// Short git revision of the benched code, suffixed with `-dirty` if it had uncommitted changes.
+    pub revision: Option<String>,
+    /// Name of the run if it was saved as a baseline.
+    pub baseline: Option<String>,
+    pub timings: Timings,
+}
+
+impl Run {
+    /// Create a run of the current revision from fresh timings.
+    pub fn new(timings: Timings, baseline: Option<String>) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        Run {
+            timestamp,
+            revision: git_revision(),
+            baseline,
+            timings,
+        }
+    }
+
+    /// Describe the run for humans, e.g. `a1b2c3d (baseline "before-refactor", 2 hours ago)`.
+    pub fn describe(&self) -> String {
+        let revision = self.revision.as_deref().unwrap_or("unknown revision");
+        let age = format_age(
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs())
+                .saturating_sub(self.timestamp),
+        );
+
+        match &self.baseline {
+            Some(name) => format!("{revision} (baseline \"{name}\", {age})"),
+            None => format!("{revision} ({age})"),
+        }
+    }
+}
+
+/// Append a run to the history of a year.
+pub fn append(year: Year, run: &Run) -> io::Result<()> {
+    let line = JsonValue::from(run)
+        .stringify()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+    fs::create_dir_all(data_dir(year))?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(get_path(year))?;
+    writeln!(file, "{line}")
+}
+
+/// Read all runs of a year, oldest first. If not present, returns an empty history.
+pub fn read(year: Year) -> Result<Vec<Run>, String> {
+    let contents = match fs::read_to_string(get_path(year)) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e.to_string()),
+    };
+
+    contents
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let json = JsonValue::from_str(line).or(Err("not valid JSON line."))?;
+            Run::try_from(&json)
+        })
+        .collect()
+}
+
+/// The run to compare against: the latest run saved under `baseline`, or the latest run if not set.
+pub fn find_reference<'a>(runs: &'a [Run], baseline: Option<&str>) -> Option<&'a Run> {
+    runs.iter()
+        .rev()
+        .find(|run| baseline.is_none_or(|name| run.baseline.as_deref() == Some(name)))
+}
+
+fn get_path(year: Year) -> String {
+    format!("{}/{HISTORY_FILE_NAME}", data_dir(year))
+}
+
+fn git_revision() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+
+    let revision = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    let is_dirty = Command::new("git")
+        .args(["status", "--porcelain", "--untracked-files=no"])
+        .output()
+        .is_ok_and(|output| !output.stdout.is_empty());
+
+    Some(if is_dirty {
+        format!("{revision}-dirty")
+    } else {
+        revision
+    })
+}
+
+fn format_age(seconds: u64) -> String {
+    match seconds {
+        0..60 => "just now".into(),
+        60..3600 => format!("{} minutes ago", seconds / 60),
+        3600..86400 => format!("{} hours ago", seconds / 3600),
+        _ => format!("{} days ago", seconds / 86400),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The change of a benched step between two runs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Change {
+    pub day: Day,
+    /// `Parse`, `Part 1` or `Part 2`.
+    pub step: &'static str,
+    /// Median execution times in nanoseconds.
+    pub before: f64,
+    pub after: f64,
+}
+
+impl Change {
+    /// Relative change in percent, positive if the step got slower.
+    pub fn percent(&self) -> f64 {
+        (self.after - self.before) / self.before * 100.0
+    }
+
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.percent() > threshold
+    }
+}
+
+/// Compare the median times of every step that was benched in both `reference` and `current`.
+pub fn compare(reference: &Timings, current: &Timings) -> Vec<Change> {
+    let mut changes = vec![];
+
+    for timing in &current.data {
+        let Some(previous) = reference.data.iter().find(|t| t.day == timing.day) else {
+            continue;
+        };
+
+        for (step, before, after) in steps(previous)
+            .into_iter()
+            .zip(steps(timing))
+            .filter_map(|((step, before), (_, after))| Some((step, before?, after?)))
+        {
+            if before > 0.0 {
+                changes.push(Change {
+                    day: timing.day,
+                    step,
+                    before,
+                    after,
+                });
+            }
+        }
+    }
+
+    changes
+}
+
+fn steps(timing: &Timing) -> [(&'static str, Option<f64>); 3] {
+    [
+        ("Parse", timing.parse.map(|s| s.nanos)),
+        ("Part 1", timing.part_1.map(|s| s.nanos)),
+        ("Part 2", timing.part_2.map(|s| s.nanos)),
+    ]
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Run> for JsonValue {
+    fn from(value: &Run) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "version".into(),
+            JsonValue::Number(f64::from(TIMINGS_VERSION)),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "revision".into(),
+            value
+                .revision
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert(
+            "baseline".into(),
+            value
+                .baseline
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.timings.data.iter().map(JsonValue::from).collect()),
+        );
+        map.insert(
+            "environment".into(),
+            value
+                .timings
+                .environment
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Run {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected run to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|v| *v as u64)
+            .ok_or("expected `run.timestamp` to be a number.")?;
+
+        let revision = json
+            .get("revision")
+            .and_then(|v| v.get::<String>())
+            .cloned();
+        let baseline = json
+            .get("baseline")
+            .and_then(|v| v.get::<String>())
+            .cloned();
+
+        let version = read_version(json.get("version"))?;
+
+        let data = json
+            .get("data")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("expected `run.data` to be an array.")?
+            .iter()
+            .map(|timing| Timing::from_json(timing, version))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Run {
+            timestamp,
+            revision,
+            baseline,
+            timings: Timings {
+                data,
+                environment: read_environment(json.get("environment"))?,
+            },
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::str::FromStr;
+
+    use tinyjson::JsonValue;
+
+    use super::{compare, find_reference, Run};
+    use crate::day;
+    use crate::template::timings::{StepTiming, Timing, Timings};
+
+    fn timing(day: u8, part_1: Option<f64>, part_2: Option<f64>) -> Timing {
+        let step = |nanos| StepTiming { nanos, samples: 1 };
+
+        Timing {
+            day: crate::template::Day::new(day).unwrap(),
+            parse: None,
+            part_1: part_1.map(step),
+            part_2: part_2.map(step),
+            parse_stats: None,
+            part_1_stats: None,
+            part_2_stats: None,
+            parse_status: None,
+            part_1_status: None,
+            part_2_status: None,
+            total_nanos: part_1.unwrap_or(0.0) + part_2.unwrap_or(0.0),
+        }
+    }
+
+    fn run(baseline: Option<&str>, data: Vec<Timing>) -> Run {
+        Run {
+            timestamp: 1_700_000_000,
+            revision: Some("a1b2c3d".into()),
+            baseline: baseline.map(Into::into),
+            timings: Timings {
+                data,
+                environment: None,
+            },
+        }
+    }
+
+    #[test]
+    fn roundtrips_runs() {
+        let original = run(Some("before"), vec![timing(1, Some(100.0), None)]);
+        let line = JsonValue::from(&original).stringify().unwrap();
+        assert!(!line.contains('\n'));
+
+        let parsed = Run::try_from(&JsonValue::from_str(&line).unwrap()).unwrap();
+        assert_eq!(parsed.timestamp, 1_700_000_000);
+        assert_eq!(parsed.revision.as_deref(), Some("a1b2c3d"));
+        assert_eq!(parsed.baseline.as_deref(), Some("before"));
+        assert_eq!(parsed.timings.data.len(), 1);
+        assert_eq!(parsed.timings.data[0].part_1.unwrap().nanos, 100.0);
+    }
+
+    #[test]
+    fn finds_reference_runs() {
+        let runs = vec![
+            run(Some("before"), vec![]),
+            run(None, vec![timing(1, None, None)]),
+        ];
+
+        assert_eq!(find_reference(&runs, None).unwrap().timings.data.len(), 1);
+        assert_eq!(
+            find_reference(&runs, Some("before"))
+                .unwrap()
+                .baseline
+                .as_deref(),
+            Some("before")
+        );
+        assert!(find_reference(&runs, Some("unknown")).is_none());
+        assert!(find_reference(&[], None).is_none());
+    }
+
+    #[test]
+    fn compares_parts_benched_in_both_runs() {
+        let reference = Timings {
+            data: vec![
+                timing(1, Some(100.0), Some(200.0)),
+                timing(2, Some(50.0), None),
+            ],
+            environment: None,
+        };
+        let current = Timings {
+            data: vec![
+                timing(1, Some(150.0), Some(190.0)),
+                timing(2, Some(50.0), Some(10.0)),
+                timing(3, Some(10.0), None),
+            ],
+            environment: None,
+        };
+
+        let changes = compare(&reference, &current);
+        assert_eq!(changes.len(), 3);
+
+        assert_eq!(changes[0].day, day!(1));
+        assert_eq!(changes[0].step, "Part 1");
+        assert_eq!(changes[0].percent(), 50.0);
+        assert!(changes[0].is_regression(10.0));
+
+        assert_eq!(changes[1].step, "Part 2");
+        assert_eq!(changes[1].percent(), -5.0);
+        assert!(!changes[1].is_regression(10.0));
+
+        assert_eq!(changes[2].day, day!(2));
+        assert!(!changes[2].is_regression(0.0));
+    }
+}
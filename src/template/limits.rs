@@ -0,0 +1,94 @@
+/// Resource limits for solutions that are run in a child process.
+/// The runner enforces the timeout by killing the child, the memory limit is applied by the solution binary itself.
+use std::env;
+use std::process::{Child, Command};
+use std::time::Duration;
+
+/// Start of the message the standard library prints to stderr when an allocation fails.
+pub const OUT_OF_MEMORY_MESSAGE: &str = "memory allocation of";
+
+/// Resource limits of a solution, see the `--timeout` and `--memory-limit` options.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Limits {
+    /// Wall-clock time the parse step and each part may take.
+    pub timeout: Option<Duration>,
+    /// Maximum memory of the solution process in megabytes.
+    pub memory_limit: Option<u64>,
+}
+
+impl Limits {
+    /// Reads the limits from the arguments passed to a solution binary.
+    pub fn from_args() -> Self {
+        let read_arg = |name: &str| -> Option<u64> {
+            env::args()
+                .skip_while(|x| x != name)
+                .nth(1)
+                .and_then(|x| x.parse().ok())
+        };
+
+        Self {
+            timeout: read_arg("--timeout").map(Duration::from_secs),
+            memory_limit: read_arg("--memory-limit"),
+        }
+    }
+
+    pub fn is_set(&self) -> bool {
+        self.timeout.is_some() || self.memory_limit.is_some()
+    }
+
+    /// Apply the memory limit to the current process, if set.
+    /// Allocations beyond the limit fail, which aborts the process instead of exhausting the memory of the machine.
+    pub fn apply(&self) {
+        if let Some(megabytes) = self.memory_limit {
+            set_memory_limit(megabytes);
+        }
+    }
+}
+
+#[cfg(unix)]
+fn set_memory_limit(megabytes: u64) {
+    let bytes = megabytes.saturating_mul(1024 * 1024) as libc::rlim_t;
+    let limit = libc::rlimit {
+        rlim_cur: bytes,
+        rlim_max: bytes,
+    };
+    // SAFETY: `setrlimit` only reads the passed struct.
+    if unsafe { libc::setrlimit(libc::RLIMIT_AS, &limit) } != 0 {
+        eprintln!("Could not set memory limit of {megabytes}MB.");
+    }
+}
+
+#[cfg(not(unix))]
+fn set_memory_limit(_megabytes: u64) {
+    eprintln!("Memory limits are not supported on this platform.");
+}
+
+/// Spawn the command in its own process group, so [`kill`] also reaches the processes it starts.
+/// This matters for `cargo run`, which runs the solution binary as its own child.
+/// Note that a child in its own process group no longer receives Ctrl+C from the terminal.
+pub fn isolate(command: &mut Command) -> &mut Command {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0)
+    }
+    #[cfg(not(unix))]
+    {
+        command
+    }
+}
+
+/// Kill a child that was spawned with [`isolate`], including the processes it started.
+pub fn kill(child: &mut Child) {
+    #[cfg(unix)]
+    {
+        // SAFETY: the child is the leader of its own process group, see `isolate`.
+        if let Ok(pid) = libc::pid_t::try_from(child.id()) {
+            unsafe {
+                libc::kill(-pid, libc::SIGKILL);
+            }
+        }
+    }
+    let _ = child.kill();
+    let _ = child.wait();
+}
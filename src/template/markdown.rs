@@ -0,0 +1,245 @@
+//! Conversion of Advent of Code puzzle pages to markdown.
+//! This only covers the small subset of HTML that puzzle descriptions use.
+
+/// Convert the puzzle descriptions and answers of a puzzle page to markdown.
+/// Everything outside of `<article>` elements and "Your puzzle answer was" paragraphs is dropped.
+pub fn puzzle_to_markdown(html: &str) -> String {
+    let mut sections: Vec<String> = vec![];
+    let mut rest = html;
+
+    loop {
+        let article = rest.find("<article");
+        let answer = rest.find("<p>Your puzzle answer was");
+
+        let (start, end_tag) = match (article, answer) {
+            (Some(a), Some(b)) if b < a => (b, "</p>"),
+            (Some(a), _) => (a, "</article>"),
+            (None, Some(b)) => (b, "</p>"),
+            (None, None) => break,
+        };
+
+        let Some(end) = rest[start..]
+            .find(end_tag)
+            .map(|x| start + x + end_tag.len())
+        else {
+            break;
+        };
+
+        sections.push(html_to_markdown(&rest[start..end]));
+        rest = &rest[end..];
+    }
+
+    let mut markdown = sections.join("\n\n");
+    markdown.push('\n');
+    markdown
+}
+
+/// Convert a HTML fragment to markdown.
+pub fn html_to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut in_pre = false;
+    let mut link: Option<String> = None;
+    let mut rest = html;
+
+    while let Some(pos) = rest.find('<') {
+        push_text(&mut out, &rest[..pos], in_pre);
+
+        let Some(end) = rest[pos..].find('>').map(|x| pos + x) else {
+            rest = &rest[pos..];
+            break;
+        };
+
+        let tag = &rest[pos + 1..end];
+        rest = &rest[end + 1..];
+
+        let is_closing = tag.starts_with('/');
+        let name = tag
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        match (name.as_str(), is_closing) {
+            ("h1" | "h2" | "h3", false) => out.push_str("\n## "),
+            ("h1" | "h2" | "h3" | "p" | "ul", true) => out.push_str("\n\n"),
+            ("p" | "ul", false) => out.push_str("\n\n"),
+            ("li", false) => out.push_str("\n- "),
+            ("pre", false) => {
+                in_pre = true;
+                out.push_str("\n\n```\n");
+            }
+            ("pre", true) => {
+                in_pre = false;
+                if !out.ends_with('\n') {
+                    out.push('\n');
+                }
+                out.push_str("```\n\n");
+            }
+            ("code", _) if !in_pre => out.push('`'),
+            ("em", _) if !in_pre => out.push('*'),
+            ("a", false) => {
+                link = get_attribute(tag, "href");
+                out.push('[');
+            }
+            ("a", true) => {
+                out.push(']');
+                if let Some(href) = link.take() {
+                    out.push_str(&format!("({href})"));
+                }
+            }
+            ("br", _) => out.push('\n'),
+            _ => {}
+        }
+    }
+
+    push_text(&mut out, rest, in_pre);
+    collapse_blank_lines(&out)
+}
+
+fn push_text(out: &mut String, text: &str, in_pre: bool) {
+    let text = decode_entities(text);
+    if in_pre {
+        out.push_str(&text);
+    } else {
+        // line breaks in regular HTML text are whitespace.
+        out.push_str(&text.replace('\n', " "));
+    }
+}
+
+fn get_attribute(tag: &str, attribute: &str) -> Option<String> {
+    let needle = format!("{attribute}=\"");
+    let start = tag.find(&needle)? + needle.len();
+    let end = tag[start..].find('"')? + start;
+    Some(decode_entities(&tag[start..end]))
+}
+
+fn decode_entities(s: &str) -> String {
+    if !s.contains('&') {
+        return s.to_string();
+    }
+
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(pos) = rest.find('&') {
+        out.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+
+        let entity = rest.find(';').map(|end| (&rest[1..end], end));
+        let decoded = entity.and_then(|(name, end)| {
+            let c = match name {
+                "lt" => '<',
+                "gt" => '>',
+                "amp" => '&',
+                "quot" => '"',
+                "apos" => '\'',
+                "nbsp" => ' ',
+                _ => name
+                    .strip_prefix("#x")
+                    .and_then(|x| u32::from_str_radix(x, 16).ok())
+                    .or_else(|| name.strip_prefix('#').and_then(|x| x.parse().ok()))
+                    .and_then(char::from_u32)?,
+            };
+            Some((c, end))
+        });
+
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+fn collapse_blank_lines(s: &str) -> String {
+    let mut out: Vec<&str> = vec![];
+    let mut in_code_block = false;
+
+    for line in s.lines() {
+        let line = if in_code_block { line } else { line.trim_end() };
+
+        if line.starts_with("```") {
+            in_code_block = !in_code_block;
+        }
+
+        let is_blank = line.trim().is_empty();
+        if !in_code_block && is_blank && out.last().is_none_or(|l| l.trim().is_empty()) {
+            continue;
+        }
+
+        out.push(if !in_code_block && !line.starts_with("```") {
+            line.trim_start()
+        } else {
+            line
+        });
+    }
+
+    while out.last().is_some_and(|l| l.trim().is_empty()) {
+        out.pop();
+    }
+
+    out.join("\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{html_to_markdown, puzzle_to_markdown};
+
+    #[test]
+    fn converts_paragraphs_and_inline_elements() {
+        let html = r#"<h2>--- Day 1: Report Repair ---</h2><p>Find the <em>two entries</em> that sum to <code>2020</code>; see <a href="/2020/about">about</a>.</p>"#;
+        assert_eq!(
+            html_to_markdown(html),
+            "## --- Day 1: Report Repair ---\n\nFind the *two entries* that sum to `2020`; see [about](/2020/about)."
+        );
+    }
+
+    #[test]
+    fn converts_code_blocks() {
+        let html = "<p>For example:</p>\n<pre><code>1721\n<em>979</em>\n366 &lt; 675\n</code></pre>\n<p>Done.</p>";
+        assert_eq!(
+            html_to_markdown(html),
+            "For example:\n\n```\n1721\n979\n366 < 675\n```\n\nDone."
+        );
+    }
+
+    #[test]
+    fn converts_lists() {
+        let html = "<ul>\n<li>one</li>\n<li><code><em>two</em></code></li>\n</ul>";
+        assert_eq!(html_to_markdown(html), "- one\n- `*two*`");
+    }
+
+    #[test]
+    fn decodes_entities() {
+        assert_eq!(
+            html_to_markdown("<p>&quot;a&quot; &amp; &#39;b&#x27; &unknown</p>"),
+            "\"a\" & 'b' &unknown"
+        );
+    }
+
+    #[test]
+    fn extracts_puzzle_sections() {
+        let html = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Test ---</h2><p>Part one.</p></article>
+<p>Your puzzle answer was <code>42</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Part two.</p></article>
+<p>Your puzzle answer was <code>43</code>.</p>
+<form method="post"><input type="hidden" name="level" value="2"/></form>
+</main></body></html>"#;
+        assert_eq!(
+            puzzle_to_markdown(html),
+            "## --- Day 1: Test ---\n\nPart one.\n\nYour puzzle answer was `42`.\n\n## --- Part Two ---\n\nPart two.\n\nYour puzzle answer was `43`.\n"
+        );
+    }
+}
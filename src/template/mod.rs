@@ -1,68 +1,153 @@
-use std::{env, fs};
+use std::{env, fs, io};
 
+pub mod answers;
 pub mod aoc_cli;
+pub mod aoc_client;
 pub mod commands;
+pub mod examples;
+pub mod limits;
+pub mod ocr;
 pub mod runner;
+pub mod submissions;
 
+pub use answer::*;
 pub use day::*;
+pub use day_selection::*;
+pub use year::*;
 
+mod answer;
+mod budgets;
 mod day;
+mod day_selection;
+mod environment;
+mod history;
+mod markdown;
 mod readme_benchmarks;
 mod run_multi;
+mod stats;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
+pub const ANSI_RED: &str = "\x1b[31m";
+pub const ANSI_GREEN: &str = "\x1b[32m";
+pub const ANSI_YELLOW: &str = "\x1b[33m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+pub fn read_file(folder: &str, puzzle: PuzzleId) -> String {
+    try_read_file(folder, puzzle).expect("could not open input file")
+}
+
+/// Helper function that reads a text file to a string, returning an error if it can not be read.
+pub fn try_read_file(folder: &str, puzzle: PuzzleId) -> io::Result<String> {
+    let cwd = env::current_dir()?;
+    let filepath = cwd.join(puzzle.data_path(folder, "txt"));
+    fs::read_to_string(filepath)
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, puzzle: PuzzleId, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
+        .join(data_dir(puzzle.year))
         .join(folder)
-        .join(format!("{day}-{part}.txt"));
+        .join(format!("{}-{part}.txt", puzzle.day));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Helper function that reads all examples of a puzzle, see [`examples`] for the manifest format.
+#[must_use]
+pub fn read_examples(puzzle: PuzzleId) -> Vec<examples::Example> {
+    examples::read(puzzle).expect("could not read examples")
+}
+
+/// Creates the constants `YEAR`, `DAY` and `PUZZLE` and sets up the input and runner for each part.
+///
+/// The year is read from the name of the solution file, e.g. `src/bin/2020_01.rs`.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// The optional `parse = <fn>` parameter adds a parse step that runs once and is timed separately.
+/// Its output is passed to both parts by reference, e.g. `solution!(7, parse = parse)` calls
+/// `part_one(&parse(input))`.
+///
+/// Additionally, this creates the constant `SOLUTION`, which registers the solution with the
+/// main binary so `cargo all` and `cargo time` can run it in-process.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+        $crate::solution!(@impl $day, [], [part_one, 1] [part_two, 2]);
     };
     ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+        $crate::solution!(@impl $day, [], [part_one, 1]);
     };
     ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+        $crate::solution!(@impl $day, [], [part_two, 2]);
+    };
+    ($day:expr, parse = $parse:expr) => {
+        $crate::solution!(@impl $day, [$parse], [part_one, 1] [part_two, 2]);
+    };
+    ($day:expr, 1, parse = $parse:expr) => {
+        $crate::solution!(@impl $day, [$parse], [part_one, 1]);
+    };
+    ($day:expr, 2, parse = $parse:expr) => {
+        $crate::solution!(@impl $day, [$parse], [part_two, 2]);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@impl $day:expr, [$($parse:expr)?], $( [$func:expr, $part:expr] )*) => {
+        /// The year of the puzzle.
+        const YEAR: $crate::template::Year = $crate::template::Year::__from_bin_path(file!());
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
+        /// The puzzle that is solved.
+        const PUZZLE: $crate::template::PuzzleId = $crate::template::PuzzleId::new(YEAR, DAY);
+
+        const _ASSERT_DAY_OF_YEAR: () = assert!(
+            YEAR.contains(DAY),
+            "the day of the solution is not a puzzle day of its year"
+        );
 
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        /// Registry entry for running this solution from the main binary.
+        #[allow(dead_code)]
+        pub const SOLUTION: $crate::template::runner::Solution = $crate::template::runner::Solution {
+            puzzle: PUZZLE,
+            run: run_parts,
+        };
+
+        fn run_parts(
+            input: &str,
+            options: $crate::template::runner::RunOptions,
+        ) -> $crate::template::runner::DayResult {
+            use $crate::template::runner::*;
+            let mut result = DayResult::default();
+            $(
+                let (parsed, parse_result) = run_parse($parse, input, options);
+                result.parse = Some(parse_result);
+                let Some(parsed) = parsed else {
+                    return result;
+                };
+                let input = &parsed;
+            )?
+            // NOTE: the closure allows deref coercion of the parsed value, e.g. from `&Vec<T>` to `&[T]`.
+            result.parts = vec![$( run_part(|input| $func(input), input, PUZZLE, $part, options), )*];
+            result
+        }
+
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let options = RunOptions::from_args();
+            options.limits.apply();
+            let input = $crate::template::read_file("inputs", PUZZLE);
+            run_parts(&input, options);
         }
     };
 }
@@ -0,0 +1,219 @@
+//! Recognizes the letters that some puzzles draw on a grid of `#` and `.`, e.g. `EHZCRJPK`.
+//! Both fonts of Advent of Code are supported: the small one is 6 rows tall and the large one 10 rows.
+//! Letters are separated by empty columns, so leading and trailing padding does not matter.
+
+/// Letters of the small font, 4 (or 5) columns wide and 6 rows tall.
+const SMALL_FONT: &[(char, &str)] = &[
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', ".###\n..#.\n..#.\n..#.\n..#.\n.###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+/// Letters of the large font, 6 columns wide and 10 rows tall.
+const LARGE_FONT: &[(char, &str)] = &[
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+/// A letter as the set pixels of its columns, without leading empty columns.
+type Glyph = Vec<Vec<bool>>;
+
+/// Convert a grid of letters to the string used for submission.
+/// Any character other than `#` (or `█`) counts as an empty pixel. Empty rows above and below the letters are ignored.
+/// Returns `None` if the grid has an unsupported height or contains an unknown letter.
+pub fn recognize(grid: &str) -> Option<String> {
+    let rows: Vec<Vec<bool>> = grid
+        .lines()
+        .map(|line| line.chars().map(is_set).collect())
+        .collect();
+
+    // drop empty rows around the letters.
+    let first = rows.iter().position(|row| row.contains(&true))?;
+    let last = rows.iter().rposition(|row| row.contains(&true))?;
+    let rows = &rows[first..=last];
+
+    let font = match rows.len() {
+        6 => SMALL_FONT,
+        10 => LARGE_FONT,
+        _ => return None,
+    };
+
+    let width = rows.iter().map(Vec::len).max()?;
+    let columns: Vec<Vec<bool>> = (0..width)
+        .map(|x| {
+            rows.iter()
+                .map(|row| row.get(x).copied().unwrap_or(false))
+                .collect()
+        })
+        .collect();
+
+    columns
+        .split(|column| !column.contains(&true))
+        .filter(|glyph| !glyph.is_empty())
+        .map(|glyph| {
+            font.iter()
+                .find(|(_, letter)| to_glyph(letter) == glyph)
+                .map(|(c, _)| *c)
+        })
+        .collect()
+}
+
+fn is_set(c: char) -> bool {
+    c == '#' || c == '█'
+}
+
+fn to_glyph(letter: &str) -> Glyph {
+    let rows: Vec<Vec<bool>> = letter
+        .lines()
+        .map(|line| line.chars().map(is_set).collect())
+        .collect();
+
+    let columns: Glyph = (0..rows[0].len())
+        .map(|x| rows.iter().map(|row| row[x]).collect())
+        .collect();
+
+    // some letters, e.g. `I`, have an empty first column.
+    columns
+        .into_iter()
+        .skip_while(|column: &Vec<bool>| !column.contains(&true))
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{recognize, LARGE_FONT, SMALL_FONT};
+
+    /// Draw letters of a font next to each other, separated by an empty column.
+    fn draw(font: &[(char, &str)], letters: &str) -> String {
+        let glyphs: Vec<Vec<&str>> = letters
+            .chars()
+            .map(|c| {
+                let (_, glyph) = font.iter().find(|(l, _)| *l == c).unwrap();
+                glyph.lines().collect()
+            })
+            .collect();
+
+        (0..glyphs[0].len())
+            .map(|y| {
+                glyphs
+                    .iter()
+                    .map(|g| format!("{}.", g[y]))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn recognizes_every_letter() {
+        for (c, glyph) in SMALL_FONT.iter().chain(LARGE_FONT) {
+            assert_eq!(recognize(glyph), Some(c.to_string()));
+        }
+    }
+
+    #[test]
+    fn recognizes_small_letters() {
+        let grid = "\
+####.#..#.####..##..###.....##.###..#..#.
+#....#..#....#.#..#.#..#.....#.#..#.#.#..
+###..####...#..#....#..#.....#.#..#.##...
+#....#..#..#...#....###......#.###..#.#..
+#....#..#.#....#..#.#.#...#..#.#....#.#..
+####.#..#.####..##..#..#...##..#....#..#.";
+        assert_eq!(recognize(grid), Some("EHZCRJPK".into()));
+    }
+
+    #[test]
+    fn recognizes_large_letters() {
+        let grid = draw(LARGE_FONT, "HKJFAKAF");
+        assert_eq!(recognize(&grid), Some("HKJFAKAF".into()));
+    }
+
+    #[test]
+    fn ignores_padding_and_other_pixels() {
+        let grid = format!("\n{}\n\n", draw(SMALL_FONT, "ICY").replace('.', " "));
+        assert_eq!(recognize(&grid), Some("ICY".into()));
+    }
+
+    #[test]
+    fn rejects_unknown_letters() {
+        assert_eq!(recognize("#\n#\n#"), None);
+        assert_eq!(recognize("#.#\n.#.\n#.#\n.#.\n#.#\n.#."), None);
+        assert_eq!(recognize(""), None);
+    }
+}
@@ -2,8 +2,10 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::runner::PartStatus;
+use crate::template::stats::{format_nanos, Stats};
+use crate::template::timings::{StepTiming, Timings};
+use crate::template::{PuzzleId, Year};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -26,8 +28,8 @@ pub struct TablePosition {
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(puzzle: PuzzleId) -> String {
+    format!("./src/bin/{}.rs", puzzle.bin_name())
 }
 
 fn locate_table(readme: &str) -> Result<TablePosition, Error> {
@@ -52,47 +54,84 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
-
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
-
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+fn construct_table(prefix: &str, years: &[(Year, Timings)]) -> String {
+    let mut lines: Vec<String> = vec![MARKER.into()];
+
+    for (i, (year, timings)) in years.iter().enumerate() {
+        if i > 0 {
+            lines.push(String::new());
+        }
+
+        lines.push(format!("{prefix} Benchmarks ({year})"));
+        lines.push(String::new());
+        lines.push("| Day | Parse | Part 1 | Part 2 |".into());
+        lines.push("| :---: | :---: | :---: | :---:  |".into());
+
+        for timing in &timings.data {
+            let path = get_path_for_bin(PuzzleId::new(*year, timing.day));
+            lines.push(format!(
+                "| [Day {}]({}) | {} | {} | {} |",
+                timing.day.into_inner(),
+                path,
+                format_step_cell(timing.parse, timing.parse_stats, timing.parse_status),
+                format_step_cell(timing.part_1, timing.part_1_stats, timing.part_1_status),
+                format_step_cell(timing.part_2, timing.part_2_stats, timing.part_2_status)
+            ));
+        }
+
+        lines.push(String::new());
+        lines.push(format!("**Total: {:.2}ms**", timings.total_millis()));
+
+        if let Some(environment) = &timings.environment {
+            lines.push(String::new());
+            lines.push(format!("_Benched on {environment}._"));
+        }
     }
 
-    lines.push(String::new());
-    lines.push(format!("**Total: {total_millis:.2}ms**"));
     lines.push(MARKER.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
+fn format_cell(step: Option<StepTiming>, stats: Option<Stats>) -> String {
+    match (step, stats) {
+        (Some(step), Some(stats)) => {
+            // the 95th percentile shows slow outliers that the median hides.
+            format!(
+                "`{} ± {}` (p95 `{}`)",
+                format_nanos(step.nanos),
+                format_nanos(stats.stddev),
+                format_nanos(stats.p95)
+            )
+        }
+        (Some(step), None) => format!("`{}`", format_nanos(step.nanos)),
+        (None, _) => "`-`".into(),
+    }
+}
+
+fn format_step_cell(
+    step: Option<StepTiming>,
+    stats: Option<Stats>,
+    status: Option<PartStatus>,
+) -> String {
+    match status {
+        Some(status) if status.is_failure() => format!("`{}`", status.label()),
+        _ => format_cell(step, stats),
+    }
+}
+
+fn update_content(s: &mut String, years: &[(Year, Timings)]) -> Result<(), Error> {
     let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+    let table = construct_table("##", years);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+/// Replace the benchmark table in the readme with one table per year.
+pub fn update(years: &[(Year, Timings)]) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, years)?;
     fs::write(path, &readme)?;
     Ok(())
 }
@@ -100,59 +139,97 @@ pub fn update(timings: Timings) -> Result<(), Error> {
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
-
-    fn get_mock_timings() -> Timings {
-        Timings {
-            data: vec![
-                Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
-                },
-                Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
-                },
-                Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
-                    total_nanos: 9e+10,
-                },
-            ],
-        }
+    use crate::{
+        day, template::environment::Environment, template::stats::Stats,
+        template::timings::StepTiming, template::timings::Timing, template::timings::Timings,
+        template::Year, year,
+    };
+
+    fn step(nanos: f64) -> StepTiming {
+        StepTiming { nanos, samples: 1 }
+    }
+
+    fn get_mock_timings() -> Vec<(Year, Timings)> {
+        vec![(
+            year!(2020),
+            Timings {
+                data: vec![
+                    Timing {
+                        day: day!(1),
+                        parse: None,
+                        part_1: Some(step(10e6)),
+                        part_2: Some(step(20e6)),
+                        parse_stats: None,
+                        part_1_stats: None,
+                        part_2_stats: None,
+                        parse_status: None,
+                        part_1_status: None,
+                        part_2_status: None,
+                        total_nanos: 3e+7,
+                    },
+                    Timing {
+                        day: day!(2),
+                        parse: Some(step(5e6)),
+                        part_1: Some(step(30e6)),
+                        part_2: Some(step(40e6)),
+                        parse_stats: None,
+                        part_1_stats: None,
+                        part_2_stats: None,
+                        parse_status: None,
+                        part_1_status: None,
+                        part_2_status: None,
+                        total_nanos: 7.5e+7,
+                    },
+                    Timing {
+                        day: day!(4),
+                        parse: None,
+                        part_1: Some(step(40e6)),
+                        part_2: Some(step(50e6)),
+                        parse_stats: None,
+                        part_1_stats: Some(Stats {
+                            median: 40_000_000.0,
+                            stddev: 1_500_000.0,
+                            p95: 43_000_000.0,
+                            ..Stats::default()
+                        }),
+                        part_2_stats: None,
+                        parse_status: None,
+                        part_1_status: None,
+                        part_2_status: None,
+                        total_nanos: 9e+7,
+                    },
+                ],
+                environment: None,
+            },
+        )]
     }
 
     #[test]
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &get_mock_timings()).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &get_mock_timings()).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.contains("## Benchmarks"), true);
+        update_content(&mut s, &get_mock_timings()).unwrap();
+        assert_eq!(s.contains("## Benchmarks (2020)"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &get_mock_timings()).unwrap();
+        update_content(&mut s, &get_mock_timings()).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,24 +237,54 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, &get_mock_timings()).unwrap();
         let expected = [
             "foo",
             "bar",
             "<!--- benchmarking table --->",
-            "## Benchmarks",
+            "## Benchmarks (2020)",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/2020_01.rs) | `-` | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/2020_02.rs) | `5.0ms` | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/2020_04.rs) | `-` | `40.0ms ± 1.5ms` (p95 `43.0ms`) | `50.0ms` |",
             "",
-            "**Total: 190.00ms**",
+            "**Total: 195.00ms**",
             "<!--- benchmarking table --->",
             "baz",
         ]
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmark_environment() {
+        let mut s = format!("{}{}", MARKER, MARKER);
+        let mut years = get_mock_timings();
+        years[0].1.environment = Some(Environment {
+            cpu: None,
+            cores: 4,
+            os: "linux x86_64".into(),
+            rustc: "rustc 1.85.0".into(),
+            profile: "release".into(),
+            features: vec![],
+        });
+        update_content(&mut s, &years).unwrap();
+        assert!(s.contains(
+            "**Total: 195.00ms**\n\n_Benched on unknown CPU (4 cores), linux x86_64, rustc 1.85.0, release profile, no features._\n"
+        ));
+    }
+
+    #[test]
+    fn format_benchmarks_for_multiple_years() {
+        let mut s = format!("{}{}", MARKER, MARKER);
+        let mut years = get_mock_timings();
+        years.push((year!(2021), years[0].1.clone()));
+        update_content(&mut s, &years).unwrap();
+        assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
+        assert_eq!(s.contains("## Benchmarks (2020)"), true);
+        assert_eq!(s.contains("## Benchmarks (2021)"), true);
+        assert_eq!(s.contains("(./src/bin/2021_04.rs)"), true);
+    }
 }
@@ -1,41 +1,155 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashSet},
+    io, panic,
+    path::Path,
+    sync::mpsc,
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    environment::Environment, try_read_file, Day, PuzzleId, Year, ANSI_BOLD, ANSI_ITALIC,
+    ANSI_RESET, ANSI_YELLOW,
+};
 
 use super::{
-    all_days,
-    timings::{Timing, Timings},
+    runner::{
+        format_parse_result, format_part_result, DayResult, OutputFormat, RunOptions, Solution,
+    },
+    timings::{StepTiming, Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+/// Determines how [`run_multi`] invokes the solutions.
+#[derive(Clone, Copy)]
+pub enum Executor<'a> {
+    /// Call the solutions compiled into the main binary directly.
+    InProcess(&'a [Solution]),
+    /// Spawn a `cargo run` child process for every solution.
+    Isolated { is_release: bool },
+}
+
+impl<'a> Executor<'a> {
+    /// Run the solutions in-process unless `is_isolated` is set.
+    /// Falls back to child processes if the year has solutions but none are compiled into the main binary,
+    /// e.g. when it was built with the `dhat-heap` feature.
+    pub fn select(
+        solutions: &'a [Solution],
+        year: Year,
+        is_isolated: bool,
+        is_release: bool,
+    ) -> Self {
+        if !is_isolated && solutions.is_empty() && has_solutions(year) {
+            eprintln!(
+                "{ANSI_YELLOW}Warning:{ANSI_RESET} no solutions are compiled into the main binary, \
+                running every solution in its own process instead."
+            );
+            return Executor::Isolated { is_release };
+        }
+
+        if is_isolated {
+            Executor::Isolated { is_release }
+        } else {
+            Executor::InProcess(solutions)
+        }
+    }
+
+    /// Run a single day without printing its output.
+    pub fn run_quiet(self, puzzle: PuzzleId, options: RunOptions) -> DayResult {
+        let options = RunOptions {
+            format: OutputFormat::Quiet,
+            ..options
+        };
 
-    let mut need_space = false;
+        match self {
+            Executor::InProcess(solutions) => run_in_process(solutions, puzzle, options),
+            Executor::Isolated { is_release } => {
+                let mut output = String::new();
+                child_commands::run_solution(puzzle, options, is_release, Some(&mut output))
+                    .unwrap_or_default()
+            }
+        }
+    }
+}
+
+/// Whether any day of the year has a solution bin.
+fn has_solutions(year: Year) -> bool {
+    year.days()
+        .any(|day| Path::new(&get_path_for_bin(PuzzleId::new(year, day))).exists())
+}
 
+/// Run the solutions for a set of days of a year.
+/// With `jobs` > 1, days run concurrently and the output of each day is buffered so it still prints in day order.
+pub fn run_multi(
+    year: Year,
+    days_to_run: &HashSet<Day>,
+    executor: Executor,
+    options: RunOptions,
+    jobs: usize,
+) -> Option<Timings> {
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
+    let days: Vec<Day> = year
+        .days()
         .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+        .collect();
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+    let mut timings: Vec<Timing> = Vec::with_capacity(days.len());
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    let mut finish_day = |day: Day, result: DayResult| {
+        if !result.parts.is_empty() || result.parse.is_some() {
+            timings.push(collect_timing(&result, day, options.is_timed));
+        }
+    };
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+    if jobs > 1 {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(jobs)
+            .build()
+            .unwrap();
+
+        pool.in_place_scope(|scope| {
+            let (tx, rx) = mpsc::channel();
+
+            for (i, day) in days.iter().enumerate() {
+                let tx = tx.clone();
+                let puzzle = PuzzleId::new(year, *day);
+                scope.spawn(move |_| {
+                    let mut output = String::new();
+                    let result = run_day(executor, puzzle, options, Some(&mut output));
+                    tx.send((i, result, output)).unwrap();
+                });
+            }
+
+            drop(tx);
+
+            // print finished days as soon as all earlier days are printed.
+            let mut pending = BTreeMap::new();
+            let mut next = 0;
+
+            for (i, result, output) in rx {
+                pending.insert(i, (result, output));
+                while let Some((result, output)) = pending.remove(&next) {
+                    if next > 0 {
+                        println!();
+                    }
+                    print!("{output}");
+                    finish_day(days[next], result);
+                    next += 1;
+                }
             }
         });
+    } else {
+        for (i, day) in days.iter().enumerate() {
+            if i > 0 {
+                println!();
+            }
+            let result = run_day(executor, PuzzleId::new(year, *day), options, None);
+            finish_day(*day, result);
+        }
+    }
 
-    if is_timed {
-        let timings = Timings { data: timings };
+    if options.is_timed {
+        let timings = Timings {
+            data: timings,
+            environment: Some(Environment::detect()),
+        };
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
@@ -46,6 +160,127 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
     }
 }
 
+/// Run a single day and print its output. If `output` is set, the output is written to it instead.
+fn run_day(
+    executor: Executor,
+    puzzle: PuzzleId,
+    options: RunOptions,
+    mut output: Option<&mut String>,
+) -> DayResult {
+    let emit = |output: &mut Option<&mut String>, line: &str| match output {
+        Some(buffer) => {
+            buffer.push_str(line);
+            buffer.push('\n');
+        }
+        None => println!("{line}"),
+    };
+
+    emit(
+        &mut output,
+        &format!("{ANSI_BOLD}Day {}{ANSI_RESET}", puzzle.day),
+    );
+    emit(&mut output, "------");
+
+    let result = match executor {
+        Executor::InProcess(solutions) if output.is_none() => {
+            run_in_process(solutions, puzzle, options)
+        }
+        Executor::InProcess(solutions) => {
+            // results are printed by the runner instead of the solution, see below.
+            let options = RunOptions {
+                format: OutputFormat::Quiet,
+                ..options
+            };
+            run_in_process(solutions, puzzle, options)
+        }
+        Executor::Isolated { is_release } => {
+            child_commands::run_solution(puzzle, options, is_release, output.as_deref_mut())
+                .unwrap()
+        }
+    };
+
+    let is_printed_by_runner = output.is_some() || matches!(executor, Executor::Isolated { .. });
+
+    if is_printed_by_runner {
+        if let Some(parse) = &result.parse {
+            emit(&mut output, &format_parse_result(parse));
+        }
+        for part in &result.parts {
+            emit(&mut output, &format_part_result(part));
+        }
+    }
+
+    if result.parts.is_empty() {
+        emit(&mut output, "Not solved.");
+    }
+
+    result
+}
+
+/// Run the registered solution for a given puzzle in the current process.
+pub fn run_in_process(solutions: &[Solution], puzzle: PuzzleId, options: RunOptions) -> DayResult {
+    let Some(solution) = solutions.iter().find(|s| s.puzzle == puzzle) else {
+        return DayResult::default();
+    };
+
+    let Ok(input) = try_read_file("inputs", puzzle) else {
+        eprintln!("Could not read input file for {puzzle}.");
+        return DayResult::default();
+    };
+
+    // a panicking solution should not abort the remaining days, same as a crashing child process.
+    panic::catch_unwind(|| (solution.run)(&input, options)).unwrap_or_default()
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn collect_timing(result: &DayResult, day: Day, is_timed: bool) -> Timing {
+    let mut timing = Timing {
+        day,
+        parse: None,
+        part_1: None,
+        part_2: None,
+        parse_stats: None,
+        part_1_stats: None,
+        part_2_stats: None,
+        parse_status: None,
+        part_1_status: None,
+        part_2_status: None,
+        total_nanos: 0_f64,
+    };
+
+    if !is_timed {
+        return timing;
+    }
+
+    if let Some(parse) = result.parse.as_ref().filter(|p| p.status.is_some()) {
+        timing.parse_status = parse.status;
+    } else if let Some(parse) = &result.parse {
+        timing.parse = Some(StepTiming::new(parse.duration, parse.samples));
+        timing.parse_stats = parse.stats;
+        timing.total_nanos += parse.duration.as_nanos() as f64;
+    }
+
+    for result in result.parts.iter().filter(|r| r.status.is_failure()) {
+        match result.part {
+            1 => timing.part_1_status = Some(result.status),
+            2 => timing.part_2_status = Some(result.status),
+            _ => continue,
+        }
+    }
+
+    for result in result.parts.iter().filter(|r| r.answer.is_some()) {
+        let step = Some(StepTiming::new(result.duration, result.samples));
+        match result.part {
+            1 => (timing.part_1, timing.part_1_stats) = (step, result.stats),
+            2 => (timing.part_2, timing.part_2_stats) = (step, result.stats),
+            _ => continue,
+        }
+        timing.total_nanos += result.duration.as_nanos() as f64;
+    }
+
+    timing
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
@@ -60,198 +295,388 @@ impl From<std::io::Error> for Error {
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(puzzle: PuzzleId) -> String {
+    format!("./src/bin/{}.rs", puzzle.bin_name())
 }
 
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as parsing their JSON output.
 pub mod child_commands {
     use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use crate::template::{
+        limits::{self, OUT_OF_MEMORY_MESSAGE},
+        runner::{DayResult, ParseResult, PartResult, PartStatus, RunOptions, Step},
+        PuzzleId,
+    };
     use std::{
         io::{BufRead, BufReader},
         path::Path,
         process::{Command, Stdio},
+        str::FromStr,
+        sync::mpsc::{self, RecvTimeoutError},
         thread,
+        time::{Duration, Instant},
     };
-
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    use tinyjson::JsonValue;
+
+    /// Run the solution bin for a given puzzle and collect the results it reports.
+    /// Other output of the bin is forwarded, or written to `output` if set.
+    pub fn run_solution(
+        puzzle: PuzzleId,
+        options: RunOptions,
+        is_release: bool,
+        mut output: Option<&mut String>,
+    ) -> Result<DayResult, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+        if !Path::new(&get_path_for_bin(puzzle)).exists() {
+            return Ok(DayResult::default());
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let bin_name = puzzle.bin_name();
+        let warmup = options.warmup.map(|x| x.to_string());
+        let memory_limit = options.limits.memory_limit.map(|x| x.to_string());
+        let mut args = vec!["run", "--quiet", "--bin", &bin_name];
 
         if is_release {
             args.push("--release");
         }
 
-        if is_timed {
+        args.push("--");
+        args.push("--json");
+
+        if options.is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
+        if let Some(warmup) = &warmup {
+            args.push("--warmup");
+            args.push(warmup);
+        }
+
+        if let Some(memory_limit) = &memory_limit {
+            args.push("--memory-limit");
+            args.push(memory_limit);
+        }
+
+        // with a timeout, build the bin first so compilation does not count towards it.
+        if options.limits.timeout.is_some() {
+            let mut build_args = vec!["build", "--quiet", "--bin", &bin_name];
+            if is_release {
+                build_args.push("--release");
+            }
+            Command::new("cargo").args(&build_args).status()?;
+        }
+
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward stderr and any stdout lines that are not results, e.g. debug output of a solution.
 
-        let mut cmd = Command::new("cargo")
+        let mut command = Command::new("cargo");
+        command
             .args(&args)
             .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+            .stderr(Stdio::piped());
+
+        // only a child that can time out is moved to its own process group, others should still receive Ctrl+C.
+        if options.limits.timeout.is_some() {
+            limits::isolate(&mut command);
+        }
+
+        let mut cmd = command.spawn()?;
 
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        let mut result = DayResult::default();
+
+        let is_captured = output.is_some();
 
         let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
+            let mut captured = vec![];
+            let mut is_out_of_memory = false;
+            stderr.lines().map_while(Result::ok).for_each(|line| {
+                is_out_of_memory |= line.starts_with(OUT_OF_MEMORY_MESSAGE);
+                if is_captured {
+                    captured.push(line);
+                } else {
+                    eprintln!("{line}");
+                }
             });
+            (captured, is_out_of_memory)
+        });
+
+        // stdout is read on a separate thread so the runner can stop waiting for a part that exceeds the timeout.
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            for line in stdout.lines().map_while(Result::ok) {
+                if tx.send(line).is_err() {
+                    break;
+                }
+            }
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+        let mut step_started = Instant::now();
+        let mut last_started = None;
+        let mut is_timed_out = false;
+
+        loop {
+            let line = match options.limits.timeout {
+                Some(timeout) => {
+                    match rx.recv_timeout(timeout.saturating_sub(step_started.elapsed())) {
+                        Ok(line) => line,
+                        Err(RecvTimeoutError::Timeout) => {
+                            is_timed_out = true;
+                            break;
+                        }
+                        Err(RecvTimeoutError::Disconnected) => break,
+                    }
+                }
+                None => match rx.recv() {
+                    Ok(line) => line,
+                    Err(_) => break,
+                },
+            };
+
+            if let Some(step) = parse_started(&line) {
+                last_started = Some(step);
+                step_started = Instant::now();
+            } else if let Some(part) = parse_result(&line) {
+                result.parts.push(part);
+            } else if let Some(parse) = parse_parse_result(&line) {
+                result.parse = Some(parse);
+            } else if let Some(output) = output.as_deref_mut() {
+                output.push_str(&line);
+                output.push('\n');
+            } else {
+                println!("{line}");
+            }
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        if is_timed_out {
+            limits::kill(&mut cmd);
+        }
 
-        Ok(output)
-    }
+        let (captured, is_out_of_memory) = thread.join().unwrap();
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
+        for line in captured {
+            if let Some(output) = output.as_deref_mut() {
+                output.push_str(&line);
+                output.push('\n');
+            }
+        }
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+        let status = cmd.wait()?;
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+        let failed_status = if is_timed_out {
+            Some(PartStatus::TimedOut)
+        } else if is_out_of_memory && !status.success() {
+            Some(PartStatus::OutOfMemory)
+        } else {
+            None
+        };
 
-                timings.total_nanos += nanos;
-            });
+        if let Some(status) = failed_status {
+            report_stopped_step(&mut result, last_started, status, step_started.elapsed());
+        }
+
+        Ok(result)
+    }
 
-        timings
+    /// Report the step that was running when the child was stopped, i.e. the last started step without a result.
+    fn report_stopped_step(
+        result: &mut DayResult,
+        last_started: Option<Step>,
+        status: PartStatus,
+        duration: Duration,
+    ) {
+        match last_started {
+            Some(Step::Parse) if result.parse.is_none() => {
+                result.parse = Some(ParseResult {
+                    status: Some(status),
+                    error: None,
+                    duration,
+                    samples: 1,
+                    stats: None,
+                });
+            }
+            Some(Step::Part(part)) if !result.parts.iter().any(|p| p.part == part) => {
+                result.parts.push(PartResult {
+                    part,
+                    status,
+                    answer: None,
+                    error: None,
+                    duration,
+                    samples: 1,
+                    stats: None,
+                });
+            }
+            _ => {}
+        }
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    /// Parse a line of output as a started [`Step`], returns `None` if the line does not announce a step.
+    pub fn parse_started(line: &str) -> Option<Step> {
+        if !line.starts_with('{') {
+            return None;
+        }
+
+        let json = JsonValue::from_str(line).ok()?;
+        Step::try_from(&json).ok()
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+    /// Parse a line of output as a [`PartResult`], returns `None` if the line is not a result.
+    pub fn parse_result(line: &str) -> Option<PartResult> {
+        if !line.starts_with('{') {
+            return None;
+        }
+
+        let json = JsonValue::from_str(line).ok()?;
+        PartResult::try_from(&json).ok()
     }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    /// Parse a line of output as a [`ParseResult`], returns `None` if the line is not a parse result.
+    pub fn parse_parse_result(line: &str) -> Option<ParseResult> {
+        if !line.starts_with('{') {
+            return None;
+        }
+
+        let json = JsonValue::from_str(line).ok()?;
+        ParseResult::try_from(&json).ok()
     }
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_parse_result, parse_result, parse_started, report_stopped_step};
+        use crate::template::runner::{DayResult, PartStatus, Step};
+        use std::time::Duration;
+
+        #[test]
+        fn parses_results() {
+            let res = parse_result(
+                r#"{"part":1,"status":"solved","answer":"0","nanos":74130,"samples":100000}"#,
+            )
+            .unwrap();
+            assert_eq!(res.part, 1);
+            assert_eq!(res.status, PartStatus::Solved);
+            assert_eq!(res.answer.unwrap(), "0");
+            assert_eq!(res.duration, Duration::from_nanos(74130));
+            assert_eq!(res.samples, 100_000);
+        }
+
+        #[test]
+        fn parses_with_patterns_in_answer() {
+            let res = parse_result(
+                r#"{"part":2,"status":"solved","answer":"@ ( ) ms (2s @ 5 samples)","nanos":100,"samples":1}"#,
+            )
+            .unwrap();
+            assert_eq!(res.part, 2);
+            assert_eq!(res.answer.unwrap(), "@ ( ) ms (2s @ 5 samples)");
+            assert_eq!(res.duration, Duration::from_nanos(100));
+        }
 
-        use crate::day;
+        #[test]
+        fn parses_missing_parts() {
+            let res = parse_result(
+                r#"{"part":1,"status":"unsolved","answer":null,"nanos":10,"samples":1}"#,
+            )
+            .unwrap();
+            assert_eq!(res.status, PartStatus::Unsolved);
+            assert_eq!(res.answer.is_none(), true);
+        }
 
         #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
+        fn parses_panicked_parts() {
+            let res = parse_result(
+                r#"{"part":2,"status":"panicked","answer":null,"error":"oops (src/bin/2020_01.rs:4:5)","nanos":10,"samples":1}"#,
+            )
+            .unwrap();
+            assert_eq!(res.status, PartStatus::Panicked);
+            assert_eq!(res.error.unwrap(), "oops (src/bin/2020_01.rs:4:5)");
+        }
+
+        #[test]
+        fn parses_parse_results() {
+            let res =
+                parse_parse_result(r#"{"parse":{"nanos":1200,"samples":1,"stats":null}}"#).unwrap();
+            assert_eq!(res.duration, Duration::from_nanos(1200));
+            assert_eq!(res.samples, 1);
+            assert_eq!(res.stats, None);
+            assert_eq!(
+                parse_result(r#"{"parse":{"nanos":1200,"samples":1,"stats":null}}"#).is_none(),
+                true
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
         }
 
         #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
+        fn parses_failed_parse_results() {
+            let res = parse_parse_result(
+                r#"{"parse":{"nanos":1200,"samples":1,"stats":null,"status":"timed_out"}}"#,
+            )
+            .unwrap();
+            assert_eq!(res.status, Some(PartStatus::TimedOut));
+        }
+
+        #[test]
+        fn parses_started_steps() {
+            assert_eq!(parse_started(r#"{"started":"parse"}"#), Some(Step::Parse));
+            assert_eq!(parse_started(r#"{"started":2}"#), Some(Step::Part(2)));
+            assert_eq!(parse_started(r#"{"started":3}"#), None);
+            assert_eq!(
+                parse_started(r#"{"parse":{"nanos":1200,"samples":1,"stats":null}}"#),
+                None
             );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
         }
 
         #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
+        fn reports_the_stopped_step() {
+            let duration = Duration::from_secs(1);
+
+            // e.g. a `solution!(1, 2)` that only has a second part.
+            let mut result = DayResult::default();
+            report_stopped_step(
+                &mut result,
+                Some(Step::Part(2)),
+                PartStatus::TimedOut,
+                duration,
+            );
+            assert_eq!(result.parts.len(), 1);
+            assert_eq!(result.parts[0].part, 2);
+            assert_eq!(result.parts[0].status, PartStatus::TimedOut);
+
+            let mut result = DayResult::default();
+            report_stopped_step(
+                &mut result,
+                Some(Step::Parse),
+                PartStatus::OutOfMemory,
+                duration,
+            );
+            assert_eq!(result.parse.unwrap().status, Some(PartStatus::OutOfMemory));
+            assert!(result.parts.is_empty());
+
+            // a step that already reported its result was not stopped.
+            let mut result = DayResult::default();
+            result.parts.push(
+                parse_result(r#"{"part":1,"status":"solved","answer":"0","nanos":10,"samples":1}"#)
+                    .unwrap(),
+            );
+            report_stopped_step(
+                &mut result,
+                Some(Step::Part(1)),
+                PartStatus::TimedOut,
+                duration,
+            );
+            assert_eq!(result.parts.len(), 1);
+            assert_eq!(result.parts[0].status, PartStatus::Solved);
+        }
+
+        #[test]
+        fn ignores_other_output() {
+            assert_eq!(
+                parse_result("Part 1: 0 (74.13ns @ 100000 samples)").is_none(),
+                true
             );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
+            assert_eq!(parse_result("{ not json").is_none(), true);
+            assert_eq!(parse_result(r#"{"debug": true}"#).is_none(), true);
         }
     }
 }
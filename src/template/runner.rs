@@ -1,24 +1,417 @@
 /// Encapsulates code that interacts with solution functions.
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::panic::{self, AssertUnwindSafe, PanicHookInfo};
+use std::sync::Once;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
+use tinyjson::JsonValue;
+
+use crate::template::answer::{Answer, PartOutput};
+use crate::template::aoc_client::{AocClient, Backend};
+use crate::template::limits::Limits;
+use crate::template::ocr;
+use crate::template::stats::{format_nanos, Stats};
+use crate::template::submissions::{self, Submission, SubmissionLog};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_cli, PuzzleId, ANSI_ITALIC, ANSI_RESET};
+
+/// Options that control how a solution part is executed.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RunOptions {
+    /// Bench the part instead of executing it once.
+    pub is_timed: bool,
+    /// Number of untimed iterations before benching. Defaults to a tenth of the bench iterations.
+    pub warmup: Option<u32>,
+    /// How results are printed to stdout.
+    pub format: OutputFormat,
+    /// Resource limits, enforced when the solution runs in a child process.
+    pub limits: Limits,
+}
+
+impl RunOptions {
+    /// Reads the options from the arguments passed to a solution binary.
+    pub fn from_args() -> Self {
+        Self {
+            is_timed: env::args().any(|x| x == "--time"),
+            warmup: env::args()
+                .skip_while(|x| x != "--warmup")
+                .nth(1)
+                .and_then(|x| x.parse().ok()),
+            format: if env::args().any(|x| x == "--json") {
+                OutputFormat::Json
+            } else {
+                OutputFormat::Human
+            },
+            limits: Limits::from_args(),
+        }
+    }
+}
+
+/// Output format of [`run_part`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Print results and timings for interactive use.
+    #[default]
+    Human,
+    /// Print one JSON object per part, see [`PartResult`].
+    Json,
+    /// Do not print results, e.g. when the caller reports them itself.
+    Quiet,
+}
+
+/// Status of a solution part after running it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PartStatus {
+    /// The part returned an answer.
+    Solved,
+    /// The part returned `None`.
+    Unsolved,
+    /// The part was killed because it exceeded the timeout.
+    TimedOut,
+    /// The part was aborted because it exceeded the memory limit.
+    OutOfMemory,
+    /// The part returned an error, see [`PartResult::error`].
+    Failed,
+    /// The part panicked, see [`PartResult::error`].
+    Panicked,
+}
+
+impl PartStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PartStatus::Solved => "solved",
+            PartStatus::Unsolved => "unsolved",
+            PartStatus::TimedOut => "timed_out",
+            PartStatus::OutOfMemory => "out_of_memory",
+            PartStatus::Failed => "failed",
+            PartStatus::Panicked => "panicked",
+        }
+    }
+
+    /// Human-readable description of the status.
+    pub fn label(self) -> &'static str {
+        match self {
+            PartStatus::Solved => "solved",
+            PartStatus::Unsolved => "unsolved",
+            PartStatus::TimedOut => "timed out",
+            PartStatus::OutOfMemory => "out of memory",
+            PartStatus::Failed => "failed",
+            PartStatus::Panicked => "panicked",
+        }
+    }
+
+    /// Whether the part did not finish.
+    pub fn is_failure(self) -> bool {
+        !matches!(self, PartStatus::Solved | PartStatus::Unsolved)
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "solved" => Some(PartStatus::Solved),
+            "unsolved" => Some(PartStatus::Unsolved),
+            "timed_out" => Some(PartStatus::TimedOut),
+            "out_of_memory" => Some(PartStatus::OutOfMemory),
+            "failed" => Some(PartStatus::Failed),
+            "panicked" => Some(PartStatus::Panicked),
+            _ => None,
+        }
+    }
+}
+
+/// The outcome of running a single solution part.
+/// In JSON mode, solution binaries print this as a single line object, e.g.
+/// `{"part":1,"status":"solved","answer":"42","nanos":74.13,"samples":10000,"stats":{...}}`.
+#[derive(Clone, Debug)]
+pub struct PartResult {
+    pub part: u8,
+    pub status: PartStatus,
+    pub answer: Option<String>,
+    /// Details of a failed part, e.g. the returned error or the message and location of a panic.
+    pub error: Option<String>,
+    /// Execution time of the part, the median if it was benched.
+    pub duration: Duration,
+    pub samples: u128,
+    /// Benchmark statistics, present if the part was benched.
+    pub stats: Option<Stats>,
+}
+
+/// The outcome of the optional parse step of a solution, see [`crate::solution`].
+/// In JSON mode, solution binaries print this as `{"parse":{"nanos":1200,"samples":1,"stats":null}}`.
+#[derive(Clone, Debug)]
+pub struct ParseResult {
+    /// Set if the parse step did not finish, e.g. because it timed out or panicked.
+    pub status: Option<PartStatus>,
+    /// Details of a failed parse step, e.g. the message and location of a panic.
+    pub error: Option<String>,
+    /// Execution time of the parse step, the median if it was benched.
+    pub duration: Duration,
+    pub samples: u128,
+    /// Benchmark statistics, present if the parse step was benched.
+    pub stats: Option<Stats>,
+}
+
+/// A step of a solution, i.e. its parse step or one of its parts.
+/// In JSON mode, solution binaries print `{"started":"parse"}` or `{"started":1}` before running a step,
+/// so a runner that kills the child knows which step did not finish.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Step {
+    Parse,
+    Part(u8),
+}
+
+/// The outcome of running a solution for a day.
+#[derive(Clone, Debug, Default)]
+pub struct DayResult {
+    /// Present if the solution has a separate parse step.
+    pub parse: Option<ParseResult>,
+    pub parts: Vec<PartResult>,
+}
+
+/// A solution that is compiled into the main binary and can be run in-process.
+/// Registry entries are created by the [`crate::solution`] macro.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub puzzle: PuzzleId,
+    pub run: fn(&str, RunOptions) -> DayResult,
+}
+
+/// Run the parse step of a solution. Returns the output of the first execution alongside its timing.
+/// The output is `None` if the parse step panicked.
+pub fn run_parse<T>(
+    func: impl Fn(&str) -> T,
+    input: &str,
+    options: RunOptions,
+) -> (Option<T>, ParseResult) {
+    let is_human = options.format == OutputFormat::Human;
+
+    announce_step(Step::Parse, options);
+
+    let timer = Instant::now();
+
+    // a panicking parse step is reported like a panicking part, the parts are skipped.
+    let run = catch_panic(|| {
+        run_timed(&func, input, options, |_| {
+            if is_human && options.is_timed {
+                print!("Parse: > {ANSI_ITALIC}benching{ANSI_RESET}");
+                let _ = stdout().flush();
+            }
+        })
+    });
+
+    let (parsed, status, error, duration, stats) = match run {
+        Ok((parsed, duration, stats)) => (Some(parsed), None, None, duration, stats),
+        Err(error) => (
+            None,
+            Some(PartStatus::Panicked),
+            Some(error),
+            timer.elapsed(),
+            None,
+        ),
+    };
+
+    let parse_result = ParseResult {
+        status,
+        error,
+        duration,
+        samples: stats.map_or(1, |s| s.samples),
+        stats,
+    };
+
+    match options.format {
+        OutputFormat::Human => print_parse_result(&parse_result),
+        OutputFormat::Json => println!("{}", JsonValue::from(&parse_result).stringify().unwrap()),
+        OutputFormat::Quiet => {}
+    }
+
+    (parsed, parse_result)
+}
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+pub fn run_part<I: Copy, R: PartOutput>(
+    func: impl Fn(I) -> R,
+    input: I,
+    puzzle: PuzzleId,
+    part: u8,
+    options: RunOptions,
+) -> PartResult {
     let part_str = format!("Part {part}");
+    let is_human = options.format == OutputFormat::Human;
+
+    announce_step(Step::Part(part), options);
+
+    let timer = Instant::now();
+
+    // a panicking part is reported as failed, so the other part still runs.
+    let run = catch_panic(|| {
+        run_timed(
+            |input| func(input).into_answer(),
+            input,
+            options,
+            |result| {
+                if is_human {
+                    match result {
+                        Ok(answer) => print_result(answer, &part_str, ""),
+                        Err(_) => print!("{part_str}: ✖"),
+                    }
+                    if options.is_timed {
+                        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+                        let _ = stdout().flush();
+                    }
+                }
+            },
+        )
+    });
+
+    let (result, status, error, duration, stats) = match run {
+        Ok((Ok(Some(answer)), duration, stats)) => {
+            (Some(answer), PartStatus::Solved, None, duration, stats)
+        }
+        Ok((Ok(None), duration, stats)) => (None, PartStatus::Unsolved, None, duration, stats),
+        Ok((Err(error), duration, stats)) => {
+            (None, PartStatus::Failed, Some(error), duration, stats)
+        }
+        Err(error) => (
+            None,
+            PartStatus::Panicked,
+            Some(error),
+            timer.elapsed(),
+            None,
+        ),
+    };
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let part_result = PartResult {
+        part,
+        status,
+        answer: result.as_ref().map(ToString::to_string),
+        error,
+        duration,
+        samples: stats.map_or(1, |s| s.samples),
+        stats,
+    };
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    match options.format {
+        OutputFormat::Human => print_part_result(&part_result),
+        OutputFormat::Json => println!("{}", JsonValue::from(&part_result).stringify().unwrap()),
+        OutputFormat::Quiet => {}
+    }
 
     if let Some(result) = result {
-        submit_result(result, day, part);
+        submit_result(result, puzzle, part);
+    }
+
+    part_result
+}
+
+/// Tell a runner that reads the JSON output which step runs next.
+fn announce_step(step: Step, options: RunOptions) {
+    if options.format == OutputFormat::Json {
+        println!("{}", JsonValue::from(step).stringify().unwrap());
+    }
+}
+
+thread_local! {
+    static IS_CATCHING_PANICS: Cell<bool> = const { Cell::new(false) };
+    static LAST_PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Run `func` and catch a panic, returning its message and location instead.
+/// The default panic output is suppressed while `func` runs, the caller is expected to report the error.
+fn catch_panic<T>(func: impl FnOnce() -> T) -> Result<T, String> {
+    // the hook is process-wide, other threads and panics outside of this function keep the default output.
+    static INSTALL_HOOK: Once = Once::new();
+    INSTALL_HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if IS_CATCHING_PANICS.with(Cell::get) {
+                LAST_PANIC.with(|p| *p.borrow_mut() = Some(describe_panic(info)));
+            } else {
+                default_hook(info);
+            }
+        }));
+    });
+
+    let was_catching = IS_CATCHING_PANICS.with(|c| c.replace(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(func));
+    IS_CATCHING_PANICS.with(|c| c.set(was_catching));
+
+    result.map_err(|_| {
+        LAST_PANIC
+            .with(|p| p.borrow_mut().take())
+            .unwrap_or_else(|| "unknown panic".into())
+    })
+}
+
+fn describe_panic(info: &PanicHookInfo) -> String {
+    let payload = info.payload();
+    let message = payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("Box<dyn Any>");
+
+    match info.location() {
+        Some(location) => format!("{message} ({location})"),
+        None => message.to_string(),
+    }
+}
+
+/// Print the final, human-readable timing line of a parse step.
+pub fn print_parse_result(result: &ParseResult) {
+    print!("\r");
+    println!("{}                ", format_parse_result(result));
+}
+
+/// Print the final, human-readable result line of a part.
+pub fn print_part_result(result: &PartResult) {
+    if result.status.is_failure() {
+        print!("\r");
+        println!("{}", format_part_result(result));
+        return;
+    }
+
+    print_result(
+        &result.answer,
+        &format!("Part {}", result.part),
+        &format_duration(result),
+    );
+}
+
+/// Format the final, human-readable timing line of a parse step, e.g. for buffered output.
+pub fn format_parse_result(result: &ParseResult) -> String {
+    let duration_str = format_timing(result.duration, result.samples, result.stats);
+
+    match (result.status, &result.error) {
+        (Some(status), Some(error)) => format!("Parse: ✖ {}: {error}", status.label()),
+        (Some(status), None) => format!("Parse: ✖ {}{duration_str}", status.label()),
+        (None, _) => format!("Parse:{duration_str}"),
+    }
+}
+
+/// Format the final, human-readable result line(s) of a part, e.g. for buffered output.
+pub fn format_part_result(result: &PartResult) -> String {
+    let part = format!("Part {}", result.part);
+    let duration_str = format_duration(result);
+
+    if result.status.is_failure() {
+        return match &result.error {
+            Some(error) => format!("{part}: ✖ {}: {error}", result.status.label()),
+            None => format!("{part}: ✖ {}{duration_str}", result.status.label()),
+        };
+    }
+
+    match &result.answer {
+        Some(answer) if answer.contains('\n') => {
+            format!(
+                "{}\n{answer}",
+                format_multiline_header(&part, answer, &duration_str)
+            )
+        }
+        Some(answer) => format!("{part}: {ANSI_BOLD}{answer}{ANSI_RESET}{duration_str}"),
+        None => format!("{part}: ✖"),
     }
 }
 
@@ -28,8 +421,9 @@ pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, da
 fn run_timed<I: Copy, T>(
     func: impl Fn(I) -> T,
     input: I,
+    options: RunOptions,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Duration, Option<Stats>) {
     let timer = Instant::now();
     let result = {
         #[cfg(feature = "dhat-heap")]
@@ -41,24 +435,32 @@ fn run_timed<I: Copy, T>(
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
-    } else {
-        (base_time, 1)
-    };
-
-    (result, run.0, run.1)
-}
+    if !options.is_timed {
+        return (result, base_time, None);
+    }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+    let stats = bench(func, input, &base_time, options.warmup);
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let median = Duration::from_nanos(stats.median.round() as u64);
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+    (result, median, Some(stats))
+}
 
+fn bench<I: Copy, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    warmup: Option<u32>,
+) -> Stats {
     let bench_iterations =
         (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
+    let warmup_iterations = warmup.map_or(bench_iterations / 10, u128::from);
+
+    for _ in 0..warmup_iterations {
+        black_box(func(black_box(input)));
+    }
+
     let mut timers: Vec<Duration> = vec![];
 
     for _ in 0..bench_iterations {
@@ -67,26 +469,29 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    // NOTE: there is at least one sample, so stats are always present.
+    Stats::from_samples(&timers).unwrap()
+}
+
+fn format_duration(result: &PartResult) -> String {
+    format_timing(result.duration, result.samples, result.stats)
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+fn format_timing(duration: Duration, samples: u128, stats: Option<Stats>) -> String {
+    match stats {
+        Some(stats) => format!(
+            " ({duration:.1?} ± {} @ {samples} samples)",
+            format_nanos(stats.stddev),
+        ),
+        None => format!(" ({duration:.1?})"),
+    }
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
-    } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+/// Multi-line answers are printed below this line. If they are letters drawn on a grid, the line shows the letters.
+fn format_multiline_header(part: &str, answer: &str, duration_str: &str) -> String {
+    match ocr::recognize(answer) {
+        Some(letters) => format!("{part}: ▼ {ANSI_BOLD}{letters}{ANSI_RESET}{duration_str}"),
+        None => format!("{part}: ▼ {duration_str}"),
     }
 }
 
@@ -95,8 +500,9 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 
     match result {
         Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
+            let answer = result.to_string();
+            if answer.contains('\n') {
+                let str = format_multiline_header(part, &answer, duration_str);
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
@@ -127,16 +533,13 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+///  2. the configured backend is available, see [`Backend`].
+///  3. the answer is not known to be wrong and the server is not cooling down, see [`SubmissionLog::check`].
+fn submit_result(result: Answer, puzzle: PuzzleId, part: u8) {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
-        return None;
+        return;
     }
 
     if args.len() < 3 {
@@ -152,14 +555,352 @@ fn submit_result<T: Display>(
     };
 
     if part_submit != part {
-        return None;
+        return;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    let Some(answer) = result.to_submission() else {
+        eprintln!("Not submitting: could not recognize the letters of the multi-line answer.");
+        return;
+    };
+
+    let mut log = SubmissionLog::read(puzzle).unwrap_or_else(|e| {
+        eprintln!("could not read submission log: {e}");
         process::exit(1);
+    });
+
+    if let Err(reason) = log.check(part, &answer, submissions::now()) {
+        eprintln!("Not submitting: {reason}");
+        return;
+    }
+
+    let message = match Backend::from_env() {
+        Backend::Native => {
+            let client = AocClient::from_env().unwrap_or_else(|e| {
+                eprintln!("could not create Advent of Code client: {e}");
+                process::exit(1);
+            });
+
+            println!("Submitting result...");
+            match client.submit(puzzle, part, &answer) {
+                Ok(message) => {
+                    println!("{message}");
+                    message
+                }
+                Err(e) => {
+                    eprintln!("failed to submit result: {e}");
+                    return;
+                }
+            }
+        }
+        Backend::AocCli => {
+            if aoc_cli::check().is_err() {
+                eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+                process::exit(1);
+            }
+
+            println!("Submitting result via aoc-cli...");
+            match aoc_cli::submit(puzzle, part, &answer) {
+                Ok(output) => String::from_utf8_lossy(&output.stdout).into_owned(),
+                Err(e) => {
+                    eprintln!("failed to call aoc-cli: {e}");
+                    return;
+                }
+            }
+        }
+    };
+
+    log.push(Submission::from_message(
+        part,
+        &answer,
+        &message,
+        submissions::now(),
+    ));
+
+    if let Err(e) = log.store(puzzle) {
+        eprintln!("could not store submission log: {e}");
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartResult> for JsonValue {
+    fn from(value: &PartResult) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "status".into(),
+            JsonValue::String(value.status.as_str().into()),
+        );
+        map.insert(
+            "answer".into(),
+            match &value.answer {
+                Some(x) => JsonValue::String(x.clone()),
+                None => JsonValue::Null,
+            },
+        );
+        if let Some(error) = &value.error {
+            map.insert("error".into(), JsonValue::String(error.clone()));
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let (nanos, samples) = (value.duration.as_nanos() as f64, value.samples as f64);
+        map.insert("nanos".into(), JsonValue::Number(nanos));
+        map.insert("samples".into(), JsonValue::Number(samples));
+        map.insert(
+            "stats".into(),
+            match &value.stats {
+                Some(x) => JsonValue::from(x),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl From<&ParseResult> for JsonValue {
+    fn from(value: &ParseResult) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        let (nanos, samples) = (value.duration.as_nanos() as f64, value.samples as f64);
+        map.insert("nanos".into(), JsonValue::Number(nanos));
+        map.insert("samples".into(), JsonValue::Number(samples));
+        map.insert(
+            "stats".into(),
+            value
+                .stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        if let Some(status) = value.status {
+            map.insert("status".into(), JsonValue::String(status.as_str().into()));
+        }
+        if let Some(error) = &value.error {
+            map.insert("error".into(), JsonValue::String(error.clone()));
+        }
+
+        let mut wrapper: HashMap<String, JsonValue> = HashMap::new();
+        wrapper.insert("parse".into(), JsonValue::Object(map));
+        JsonValue::Object(wrapper)
     }
+}
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+impl TryFrom<&JsonValue> for ParseResult {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .and_then(|v| v.get("parse"))
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .ok_or("Expected parse result to be a JSON object with key `parse`.")?;
+
+        let nanos = json
+            .get("nanos")
+            .and_then(|v| v.get::<f64>())
+            .ok_or("Expected parse.nanos to be a number.")?;
+
+        let samples = json
+            .get("samples")
+            .and_then(|v| v.get::<f64>())
+            .ok_or("Expected parse.samples to be a number.")?;
+
+        let stats = match json.get("stats") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(Stats::try_from(v)?),
+        };
+
+        let status = match json.get("status") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(
+                v.get::<String>()
+                    .and_then(|v| PartStatus::parse(v))
+                    .ok_or("Expected parse.status to be a known status.")?,
+            ),
+        };
+
+        let error = json.get("error").and_then(|v| v.get::<String>()).cloned();
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let result = ParseResult {
+            status,
+            error,
+            duration: Duration::from_nanos(*nanos as u64),
+            samples: *samples as u128,
+            stats,
+        };
+
+        Ok(result)
+    }
+}
+
+impl From<Step> for JsonValue {
+    fn from(value: Step) -> Self {
+        let step = match value {
+            Step::Parse => JsonValue::String("parse".into()),
+            Step::Part(part) => JsonValue::Number(f64::from(part)),
+        };
+
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("started".into(), step);
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Step {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let step = value
+            .get::<HashMap<String, JsonValue>>()
+            .and_then(|v| v.get("started"))
+            .ok_or("Expected step to be a JSON object with key `started`.")?;
+
+        match step {
+            JsonValue::String(s) if s == "parse" => Ok(Step::Parse),
+            JsonValue::Number(n) if *n == 1.0 => Ok(Step::Part(1)),
+            JsonValue::Number(n) if *n == 2.0 => Ok(Step::Part(2)),
+            _ => Err("Expected started to be `parse`, 1 or 2.".into()),
+        }
+    }
+}
+
+impl TryFrom<&JsonValue> for PartResult {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part result to be a JSON object.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .filter(|v| **v == 1.0 || **v == 2.0)
+            .ok_or("Expected result.part to be 1 or 2.")?;
+
+        let status = json
+            .get("status")
+            .and_then(|v| v.get::<String>())
+            .and_then(|v| PartStatus::parse(v))
+            .ok_or("Expected result.status to be a known status.")?;
+
+        let answer = json
+            .get("answer")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected result.answer to be null or string.")?;
+
+        let error = json.get("error").and_then(|v| v.get::<String>()).cloned();
+
+        let nanos = json
+            .get("nanos")
+            .and_then(|v| v.get::<f64>())
+            .ok_or("Expected result.nanos to be a number.")?;
+
+        let samples = json
+            .get("samples")
+            .and_then(|v| v.get::<f64>())
+            .ok_or("Expected result.samples to be a number.")?;
+
+        let stats = match json.get("stats") {
+            None => None,
+            Some(v) if v.is_null() => None,
+            Some(v) => Some(Stats::try_from(v)?),
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let result = PartResult {
+            part: *part as u8,
+            status,
+            answer: answer.cloned(),
+            error,
+            duration: Duration::from_nanos(*nanos as u64),
+            samples: *samples as u128,
+            stats,
+        };
+
+        Ok(result)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{run_parse, run_part, OutputFormat, PartStatus, RunOptions};
+    use crate::template::{Answer, PuzzleId};
+    use crate::{day, year};
+
+    fn quiet() -> RunOptions {
+        RunOptions {
+            format: OutputFormat::Quiet,
+            ..RunOptions::default()
+        }
+    }
+
+    #[test]
+    fn reports_panicking_parts() {
+        let puzzle = PuzzleId::new(year!(2020), day!(1));
+        let result = run_part(
+            |input: &str| -> Option<u32> { Some(input.parse().unwrap()) },
+            "x",
+            puzzle,
+            1,
+            quiet(),
+        );
+        assert_eq!(result.status, PartStatus::Panicked);
+        assert_eq!(result.answer, None);
+        let error = result.error.unwrap();
+        assert!(error.starts_with("called `Result::unwrap()` on an `Err` value"));
+        assert!(error.contains("src/template/runner.rs"));
+
+        // later parts are not affected by the panic.
+        let result = run_part(|_: &str| Some(2), "x", puzzle, 2, quiet());
+        assert_eq!(result.status, PartStatus::Solved);
+        assert_eq!(result.error, None);
+    }
+
+    #[test]
+    fn reports_panicking_parse_steps() {
+        let (parsed, result) = run_parse(|input| input.parse::<u32>().unwrap(), "x", quiet());
+        assert_eq!(parsed, None);
+        assert_eq!(result.status, Some(PartStatus::Panicked));
+        assert!(result
+            .error
+            .unwrap()
+            .starts_with("called `Result::unwrap()` on an `Err` value"));
+
+        let (parsed, result) = run_parse(|input| input.len(), "x", quiet());
+        assert_eq!(parsed, Some(1));
+        assert_eq!(result.status, None);
+    }
+
+    #[test]
+    fn reports_errors_of_parts() {
+        let puzzle = PuzzleId::new(year!(2020), day!(1));
+        let result = run_part(
+            |input: &str| input.parse::<u128>().map_err(|_| "not a number"),
+            "x",
+            puzzle,
+            1,
+            quiet(),
+        );
+        assert_eq!(result.status, PartStatus::Failed);
+        assert_eq!(result.answer, None);
+        assert_eq!(result.error.unwrap(), "not a number");
+
+        let result = run_part(
+            |_: &str| Ok::<_, String>(Answer::grid(["#.", ".#"])),
+            "x",
+            puzzle,
+            2,
+            quiet(),
+        );
+        assert_eq!(result.status, PartStatus::Solved);
+        assert_eq!(result.answer.unwrap(), "#.\n.#");
+    }
 }
@@ -0,0 +1,223 @@
+/// Summary statistics for benchmark samples.
+use std::{collections::HashMap, time::Duration};
+use tinyjson::JsonValue;
+
+/// Multiplier of the interquartile range that determines which samples are considered outliers.
+const OUTLIER_FENCE: f64 = 1.5;
+
+/// Summary statistics of a set of benchmark samples. All durations are in nanoseconds.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Stats {
+    pub mean: f64,
+    pub median: f64,
+    pub min: f64,
+    pub max: f64,
+    pub stddev: f64,
+    pub p95: f64,
+    /// Number of samples taken, including outliers.
+    pub samples: u128,
+    /// Number of samples that were rejected as outliers.
+    pub outliers: u128,
+}
+
+impl Stats {
+    /// Compute statistics for a set of samples.
+    /// Samples outside of the Tukey fences (1.5 × IQR below the first or above the third quartile) are rejected
+    /// before computing the statistics, so a single hiccup of the machine does not skew the result.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        #[allow(clippy::cast_precision_loss)]
+        let mut sorted: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+
+        if sorted.is_empty() {
+            return None;
+        }
+
+        sorted.sort_unstable_by(f64::total_cmp);
+
+        let q1 = percentile(&sorted, 25.0);
+        let q3 = percentile(&sorted, 75.0);
+        let iqr = q3 - q1;
+        let (lower, upper) = (q1 - OUTLIER_FENCE * iqr, q3 + OUTLIER_FENCE * iqr);
+
+        let kept: Vec<f64> = sorted
+            .iter()
+            .copied()
+            .filter(|x| *x >= lower && *x <= upper)
+            .collect();
+
+        #[allow(clippy::cast_precision_loss)]
+        let n = kept.len() as f64;
+        let mean = kept.iter().sum::<f64>() / n;
+        let variance = if kept.len() > 1 {
+            kept.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)
+        } else {
+            0.0
+        };
+
+        Some(Stats {
+            mean,
+            median: percentile(&kept, 50.0),
+            min: kept[0],
+            max: kept[kept.len() - 1],
+            stddev: variance.sqrt(),
+            p95: percentile(&kept, 95.0),
+            samples: samples.len() as u128,
+            outliers: (sorted.len() - kept.len()) as u128,
+        })
+    }
+}
+
+/// Linearly interpolated percentile of sorted, non-empty values.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    #[allow(clippy::cast_precision_loss)]
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let (lo, hi) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lo] + (sorted[hi] - sorted[lo]) * (rank - rank.floor())
+}
+
+/// Format a duration in nanoseconds like the `Debug` output of [`Duration`], e.g. `74.1ns` or `1.5µs`.
+/// Unlike a [`Duration`], fractions of a nanosecond are kept, e.g. of a benched median.
+pub fn format_nanos(nanos: f64) -> String {
+    for (factor, unit) in [(1.0, "ns"), (1e3, "µs"), (1e6, "ms")] {
+        let value = nanos / factor;
+        // values that round up to 1000 are shown in the next unit, e.g. `999.96ns` as `1.0µs`.
+        if (value * 10.0).round() < 10_000.0 {
+            return format!("{value:.1}{unit}");
+        }
+    }
+
+    format!("{:.1}s", nanos / 1e9)
+}
+
+/// Parse a duration formatted like [`format_nanos`] does, e.g. `74.1ns`, `1.5µs`, `250ms` or `1s`, to nanoseconds.
+pub fn parse_nanos(formatted: &str) -> Option<f64> {
+    let formatted = formatted.trim();
+    let split = formatted.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+    let (number, unit) = formatted.split_at(split);
+
+    let factor = match unit {
+        "ns" => 1.0,
+        "µs" | "us" => 1e3,
+        "ms" => 1e6,
+        "s" => 1e9,
+        _ => return None,
+    };
+
+    Some(number.parse::<f64>().ok()? * factor)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Stats> for JsonValue {
+    fn from(value: &Stats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("mean".into(), JsonValue::Number(value.mean));
+        map.insert("median".into(), JsonValue::Number(value.median));
+        map.insert("min".into(), JsonValue::Number(value.min));
+        map.insert("max".into(), JsonValue::Number(value.max));
+        map.insert("stddev".into(), JsonValue::Number(value.stddev));
+        map.insert("p95".into(), JsonValue::Number(value.p95));
+        #[allow(clippy::cast_precision_loss)]
+        let (samples, outliers) = (value.samples as f64, value.outliers as f64);
+        map.insert("samples".into(), JsonValue::Number(samples));
+        map.insert("outliers".into(), JsonValue::Number(outliers));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Stats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected stats.{key} to be a number."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let stats = Stats {
+            mean: number("mean")?,
+            median: number("median")?,
+            min: number("min")?,
+            max: number("max")?,
+            stddev: number("stddev")?,
+            p95: number("p95")?,
+            samples: number("samples")? as u128,
+            outliers: number("outliers")? as u128,
+        };
+
+        Ok(stats)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_nanos, parse_nanos, Stats};
+    use std::time::Duration;
+
+    fn samples(nanos: &[u64]) -> Vec<Duration> {
+        nanos.iter().map(|x| Duration::from_nanos(*x)).collect()
+    }
+
+    #[test]
+    fn computes_stats() {
+        let stats = Stats::from_samples(&samples(&[10, 20, 30, 40, 50])).unwrap();
+        assert_eq!(stats.mean, 30.0);
+        assert_eq!(stats.median, 30.0);
+        assert_eq!(stats.min, 10.0);
+        assert_eq!(stats.max, 50.0);
+        assert_eq!(stats.p95, 48.0);
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.outliers, 0);
+        assert!((stats.stddev - 15.811_388).abs() < 1.0e-6);
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let stats = Stats::from_samples(&samples(&[10, 11, 10, 12, 11, 10, 1000])).unwrap();
+        assert_eq!(stats.max, 12.0);
+        assert_eq!(stats.samples, 7);
+        assert_eq!(stats.outliers, 1);
+    }
+
+    #[test]
+    fn handles_single_sample() {
+        let stats = Stats::from_samples(&samples(&[42])).unwrap();
+        assert_eq!(stats.median, 42.0);
+        assert_eq!(stats.stddev, 0.0);
+    }
+
+    #[test]
+    fn handles_no_samples() {
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn parses_nanos() {
+        assert_eq!(parse_nanos("74.1ns"), Some(74.1));
+        assert_eq!(parse_nanos("1.5µs"), Some(1_500.0));
+        assert_eq!(parse_nanos("250ms"), Some(250_000_000.0));
+        assert_eq!(parse_nanos("1s"), Some(1_000_000_000.0));
+        assert_eq!(parse_nanos("1"), None);
+        assert_eq!(parse_nanos("1h"), None);
+    }
+
+    #[test]
+    fn formats_nanos() {
+        assert_eq!(format_nanos(74.13), "74.1ns");
+        assert_eq!(format_nanos(999.96), "1.0µs");
+        assert_eq!(format_nanos(74_130.0), "74.1µs");
+        assert_eq!(format_nanos(250_000_000.0), "250.0ms");
+        assert_eq!(format_nanos(2_500_000_000.0), "2.5s");
+    }
+}
@@ -0,0 +1,425 @@
+/// Log of submitted answers, used to avoid re-submitting answers that are known to be wrong.
+/// Submissions for each day live in a JSON file in `data/<year>/submissions`, e.g. `data/2020/submissions/01.json`.
+use std::{
+    collections::HashMap,
+    fs, io,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{data_dir, PuzzleId};
+
+static SUBMISSIONS_DIR: &str = "submissions";
+
+/// Verdict of the server for a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+    /// The answer was submitted too recently after the previous one and was not checked.
+    Wait,
+    /// The part was already solved, the answer was not checked.
+    AlreadySolved,
+    Unknown,
+}
+
+impl Verdict {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+            Verdict::Wrong => "wrong",
+            Verdict::Wait => "wait",
+            Verdict::AlreadySolved => "already_solved",
+            Verdict::Unknown => "unknown",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "correct" => Some(Verdict::Correct),
+            "too_high" => Some(Verdict::TooHigh),
+            "too_low" => Some(Verdict::TooLow),
+            "wrong" => Some(Verdict::Wrong),
+            "wait" => Some(Verdict::Wait),
+            "already_solved" => Some(Verdict::AlreadySolved),
+            "unknown" => Some(Verdict::Unknown),
+            _ => None,
+        }
+    }
+
+    /// Read the verdict from the response message of the server.
+    pub fn from_message(message: &str) -> Self {
+        let message = message.to_lowercase();
+
+        if message.contains("that's the right answer") {
+            Verdict::Correct
+        } else if message.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if message.contains("your answer is too low") {
+            Verdict::TooLow
+        } else if message.contains("that's not the right answer") {
+            Verdict::Wrong
+        } else if message.contains("you gave an answer too recently") {
+            Verdict::Wait
+        } else if message.contains("did you already complete it") {
+            Verdict::AlreadySolved
+        } else {
+            Verdict::Unknown
+        }
+    }
+
+    /// Whether the server checked the answer and rejected it.
+    pub fn is_rejected(self) -> bool {
+        matches!(self, Verdict::TooHigh | Verdict::TooLow | Verdict::Wrong)
+    }
+}
+
+/// A single submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// Seconds after `timestamp` before the server accepts the next answer.
+    pub cooldown: u64,
+}
+
+impl Submission {
+    /// Create a submission from the response message of the server.
+    pub fn from_message(part: u8, answer: &str, message: &str, timestamp: u64) -> Self {
+        Submission {
+            part,
+            answer: answer.to_string(),
+            verdict: Verdict::from_message(message),
+            timestamp,
+            cooldown: parse_cooldown(message),
+        }
+    }
+}
+
+/// All submissions of a day, in submission order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SubmissionLog {
+    pub submissions: Vec<Submission>,
+}
+
+impl SubmissionLog {
+    /// Read the submission log of a day. If nothing was submitted yet, returns an empty log.
+    pub fn read(puzzle: PuzzleId) -> Result<Self, String> {
+        match fs::read_to_string(get_path(puzzle)) {
+            Ok(s) => SubmissionLog::try_from(s),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(SubmissionLog::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Store the submission log of a day.
+    pub fn store(&self, puzzle: PuzzleId) -> Result<(), io::Error> {
+        fs::create_dir_all(format!("{}/{SUBMISSIONS_DIR}", data_dir(puzzle.year)))?;
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(get_path(puzzle))?;
+        json.format_to(&mut file)
+    }
+
+    pub fn push(&mut self, submission: Submission) {
+        self.submissions.push(submission);
+    }
+
+    /// Check if an answer should be submitted. Returns the reason if it should not be.
+    pub fn check(&self, part: u8, answer: &str, now: u64) -> Result<(), String> {
+        if let Some(ready_at) = self
+            .submissions
+            .iter()
+            .map(|s| s.timestamp + s.cooldown)
+            .max()
+            .filter(|ready_at| *ready_at > now)
+        {
+            return Err(format!(
+                "the server accepts the next answer in {}s.",
+                ready_at - now
+            ));
+        }
+
+        let submissions = self.submissions.iter().filter(|s| s.part == part);
+
+        for submission in submissions.clone() {
+            match submission.verdict {
+                Verdict::Correct => {
+                    return Err(format!(
+                        "part {part} was already solved with answer {}.",
+                        submission.answer
+                    ))
+                }
+                v if v.is_rejected() && submission.answer == answer => {
+                    return Err(format!("answer {answer} was already rejected."))
+                }
+                _ => {}
+            }
+        }
+
+        let Ok(value) = answer.parse::<i128>() else {
+            return Ok(());
+        };
+
+        let numeric = |verdict: Verdict| {
+            submissions
+                .clone()
+                .filter(move |s| s.verdict == verdict)
+                .filter_map(|s| s.answer.parse::<i128>().ok())
+        };
+
+        if let Some(upper) = numeric(Verdict::TooHigh).min().filter(|x| value >= *x) {
+            return Err(format!(
+                "answer {answer} is not lower than {upper}, which was too high."
+            ));
+        }
+
+        if let Some(lower) = numeric(Verdict::TooLow).max().filter(|x| value <= *x) {
+            return Err(format!(
+                "answer {answer} is not higher than {lower}, which was too low."
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Current time in seconds since the unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// Read the waiting time from messages like "You have 1m 34s left to wait."
+/// or "please wait one minute before trying again".
+fn parse_cooldown(message: &str) -> u64 {
+    let message = message.to_lowercase();
+
+    if let Some(end) = message.find(" left to wait") {
+        let start = message[..end].rfind("you have ").map_or(0, |x| x + 9);
+        return message[start..end]
+            .split_whitespace()
+            .filter_map(|token| {
+                let (value, unit) = token.split_at(token.find(|c: char| !c.is_ascii_digit())?);
+                let value: u64 = value.parse().ok()?;
+                match unit {
+                    "h" => Some(value * 3600),
+                    "m" => Some(value * 60),
+                    "s" => Some(value),
+                    _ => None,
+                }
+            })
+            .sum();
+    }
+
+    if let Some(start) = message.find("please wait ") {
+        let mut tokens = message[start + 12..].split_whitespace();
+        let count = match tokens.next() {
+            Some("one") => 1,
+            Some("two") => 2,
+            Some("five") => 5,
+            Some(x) => x.parse().unwrap_or(0),
+            None => 0,
+        };
+        return match tokens.next() {
+            Some(unit) if unit.starts_with("minute") => count * 60,
+            Some(unit) if unit.starts_with("second") => count,
+            _ => 0,
+        };
+    }
+
+    0
+}
+
+fn get_path(puzzle: PuzzleId) -> String {
+    puzzle.data_path(SUBMISSIONS_DIR, "json")
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.as_str().into()),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        let (timestamp, cooldown) = (value.timestamp as f64, value.cooldown as f64);
+        map.insert("timestamp".into(), JsonValue::Number(timestamp));
+        map.insert("cooldown".into(), JsonValue::Number(cooldown));
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected submission to be an object.")?;
+
+        let read_number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>())
+                .copied()
+                .ok_or(format!("expected `{key}` to be a number."))
+        };
+
+        let read_string = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>())
+                .cloned()
+                .ok_or(format!("expected `{key}` to be a string."))
+        };
+
+        let verdict = read_string("verdict")?;
+
+        let part = read_number("part")?;
+        if !(part == 1.0 || part == 2.0) {
+            return Err("expected `part` to be 1 or 2.".into());
+        }
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(Submission {
+            part: part as u8,
+            answer: read_string("answer")?,
+            verdict: Verdict::parse(&verdict).ok_or(format!("unknown verdict `{verdict}`."))?,
+            timestamp: read_number("timestamp")? as u64,
+            cooldown: read_number("cooldown")? as u64,
+        })
+    }
+}
+
+impl From<&SubmissionLog> for JsonValue {
+    fn from(value: &SubmissionLog) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert(
+            "submissions".into(),
+            JsonValue::Array(value.submissions.iter().map(JsonValue::from).collect()),
+        );
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for SubmissionLog {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let submissions = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("submissions")
+            .ok_or("expected JSON document to contain key `submissions`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `submissions` to be an array.")?
+            .iter()
+            .map(Submission::try_from)
+            .collect::<Result<_, _>>()?;
+
+        Ok(SubmissionLog { submissions })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_cooldown, Submission, SubmissionLog, Verdict};
+    use tinyjson::JsonValue;
+
+    fn log(submissions: &[(u8, &str, Verdict)]) -> SubmissionLog {
+        SubmissionLog {
+            submissions: submissions
+                .iter()
+                .map(|(part, answer, verdict)| Submission {
+                    part: *part,
+                    answer: answer.to_string(),
+                    verdict: *verdict,
+                    timestamp: 1000,
+                    cooldown: 60,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn reads_verdicts_from_messages() {
+        let too_high = "That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data. Please wait one minute before trying again. [[Return to Day 1]](/2020/day/1)";
+        let submission = Submission::from_message(1, "42", too_high, 0);
+        assert_eq!(submission.verdict, Verdict::TooHigh);
+        assert_eq!(submission.cooldown, 60);
+
+        let wait = "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 34s left to wait.";
+        let submission = Submission::from_message(1, "42", wait, 0);
+        assert_eq!(submission.verdict, Verdict::Wait);
+        assert_eq!(submission.cooldown, 94);
+
+        assert_eq!(
+            Verdict::from_message("That's the right answer! You are one gold star closer."),
+            Verdict::Correct
+        );
+        assert_eq!(
+            Verdict::from_message(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            ),
+            Verdict::AlreadySolved
+        );
+        assert_eq!(
+            parse_cooldown("Please wait 5 minutes before trying again."),
+            300
+        );
+    }
+
+    #[test]
+    fn respects_cooldown() {
+        let log = log(&[(1, "1", Verdict::Wrong)]);
+        assert!(log.check(1, "2", 1059).is_err());
+        assert!(log.check(1, "2", 1060).is_ok());
+    }
+
+    #[test]
+    fn refuses_known_answers() {
+        let log = log(&[
+            (1, "abc", Verdict::Wrong),
+            (1, "50", Verdict::TooHigh),
+            (1, "10", Verdict::TooLow),
+            (1, "20", Verdict::Wait),
+            (2, "7", Verdict::Correct),
+        ]);
+        assert!(log.check(1, "abc", 2000).is_err());
+        assert!(log.check(1, "50", 2000).is_err());
+        assert!(log.check(1, "51", 2000).is_err());
+        assert!(log.check(1, "10", 2000).is_err());
+        assert!(log.check(1, "-3", 2000).is_err());
+        assert!(log.check(1, "20", 2000).is_ok());
+        assert!(log.check(1, "abd", 2000).is_ok());
+        assert!(log.check(2, "8", 2000).is_err());
+    }
+
+    #[test]
+    fn roundtrips_submissions() {
+        let log = log(&[(1, "10", Verdict::TooLow), (2, "ABC", Verdict::Correct)]);
+        let json = JsonValue::from(&log).stringify().unwrap();
+        assert_eq!(SubmissionLog::try_from(json).unwrap(), log);
+
+        let json = JsonValue::from(&log)
+            .stringify()
+            .unwrap()
+            .replace(r#""part":2"#, r#""part":3"#);
+        assert!(SubmissionLog::try_from(json).is_err());
+    }
+}
@@ -1,16 +1,51 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fs, io::Error, str::FromStr, time::Duration};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{
+    data_dir,
+    environment::Environment,
+    runner::PartStatus,
+    stats::{parse_nanos, Stats},
+    Day, Year, ANSI_RESET, ANSI_YELLOW,
+};
+
+static TIMINGS_FILE_NAME: &str = "timings.json";
+
+/// Version of the JSON schema of stored timings.
+/// Version 1 stored the duration of every step as a formatted string like `"74.1ns"`, files without a version are
+/// version 1 and are migrated on read.
+pub const TIMINGS_VERSION: u32 = 2;
+
+/// Execution time of a single step of a day, i.e. its parse step or one of its parts.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct StepTiming {
+    /// Execution time in nanoseconds, the median if the step was benched.
+    pub nanos: f64,
+    pub samples: u128,
+}
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+impl StepTiming {
+    pub fn new(duration: Duration, samples: u128) -> Self {
+        #[allow(clippy::cast_precision_loss)]
+        let nanos = duration.as_nanos() as f64;
+        StepTiming { nanos, samples }
+    }
+}
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    pub parse: Option<StepTiming>,
+    pub part_1: Option<StepTiming>,
+    pub part_2: Option<StepTiming>,
+    pub parse_stats: Option<Stats>,
+    pub part_1_stats: Option<Stats>,
+    pub part_2_stats: Option<Stats>,
+    /// Set if the step did not finish, e.g. because it timed out.
+    pub parse_status: Option<PartStatus>,
+    pub part_1_status: Option<PartStatus>,
+    pub part_2_status: Option<PartStatus>,
     pub total_nanos: f64,
 }
 
@@ -19,40 +54,131 @@ pub struct Timing {
 #[derive(Clone, Debug, Default)]
 pub struct Timings {
     pub data: Vec<Timing>,
+    /// The machine and build the timings were measured with. Not present for timings stored before it was recorded.
+    pub environment: Option<Environment>,
+}
+
+/// How `cargo time --store` combines new timings of a day with the stored ones.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MergePolicy {
+    /// Replace the stored timing with the new one.
+    #[default]
+    Newest,
+    /// Keep whichever timing is faster.
+    Best,
+    /// Store the median of the last `n` runs, including the new one.
+    Median(usize),
+}
+
+/// Number of runs [`MergePolicy::Median`] takes into account if not specified.
+const DEFAULT_MEDIAN_RUNS: usize = 5;
+
+impl FromStr for MergePolicy {
+    type Err = String;
+
+    /// Parses `newest`, `best`, `median` or `median:<runs>`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            None if s == "newest" => Ok(MergePolicy::Newest),
+            None if s == "best" => Ok(MergePolicy::Best),
+            None if s == "median" => Ok(MergePolicy::Median(DEFAULT_MEDIAN_RUNS)),
+            Some(("median", runs)) => match runs.parse() {
+                Ok(runs) if runs > 0 => Ok(MergePolicy::Median(runs)),
+                _ => Err(format!("expected `{runs}` to be a number of runs.")),
+            },
+            _ => Err("expected `newest`, `best`, `median` or `median:<runs>`.".into()),
+        }
+    }
 }
 
 impl Timings {
-    /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    /// Dehydrate timings of a year to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        fs::create_dir_all(data_dir(year))?;
+        let mut file = fs::File::create(get_path(year))?;
         json.format_to(&mut file)
     }
 
-    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
+    /// Rehydrate timings of a year from a JSON file. If not present, returns empty timings.
+    pub fn read_from_file(year: Year) -> Self {
+        fs::read_to_string(get_path(year))
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from)
             .unwrap_or_default()
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// Warns if timings of `self` are kept that were measured in a different environment than `new`.
     pub fn merge(&self, new: &Self) -> Self {
+        self.merge_with(new, MergePolicy::Newest, &[])
+    }
+
+    /// Merge two sets of timings, combining days that are present in both according to `policy`.
+    /// `previous` are the timings of earlier runs, newest last, and are only used by [`MergePolicy::Median`].
+    /// A complete day is never replaced by a day that did not solve both parts.
+    pub fn merge_with(&self, new: &Self, policy: MergePolicy, previous: &[Timings]) -> Self {
         let mut data: Vec<Timing> = vec![];
+        let mut kept_days = 0;
+
+        for timing in &self.data {
+            if !new.data.iter().any(|t| t.day == timing.day) {
+                data.push(timing.clone());
+                kept_days += 1;
+            }
+        }
 
         for timing in &new.data {
-            data.push(timing.clone());
+            if let Some(stored) = self.data.iter().find(|t| t.day == timing.day) {
+                let is_partial = stored.is_complete() && !timing.is_complete();
+                let is_slower = policy == MergePolicy::Best
+                    && stored.solved_parts() == timing.solved_parts()
+                    && stored.total_nanos <= timing.total_nanos;
+
+                if is_partial {
+                    eprintln!(
+                        "{ANSI_YELLOW}Warning:{ANSI_RESET} kept the stored timing of day {}, the new run did not solve both parts.",
+                        timing.day
+                    );
+                }
+
+                if is_partial || is_slower {
+                    data.push(stored.clone());
+                    kept_days += 1;
+                    continue;
+                }
+            }
+
+            data.push(match policy {
+                MergePolicy::Median(runs) => median_timing(timing, previous, runs),
+                _ => timing.clone(),
+            });
         }
 
-        for timing in &self.data {
-            if !data.iter().any(|t| t.day == timing.day) {
-                data.push(timing.clone());
+        if kept_days > 0 && !new.data.is_empty() {
+            let differences = self.environment_differences(new);
+            if !differences.is_empty() {
+                eprintln!(
+                    "{ANSI_YELLOW}Warning:{ANSI_RESET} {kept_days} stored day(s) were benched in a different environment \
+                    ({} differ). Run `cargo time --all --store` to bench all days in the same environment.",
+                    differences.join(", ")
+                );
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
-        Timings { data }
+        data.sort_unstable_by_key(|t| t.day);
+        Timings {
+            data,
+            environment: new.environment.clone().or_else(|| self.environment.clone()),
+        }
+    }
+
+    /// Names of the environment properties that differ between two sets of timings, empty if either is unknown.
+    pub fn environment_differences(&self, other: &Self) -> Vec<&'static str> {
+        match (&self.environment, &other.environment) {
+            (Some(a), Some(b)) => a.differences(b),
+            _ => vec![],
+        }
     }
 
     /// Sum up total duration of timings as millis.
@@ -61,23 +187,101 @@ impl Timings {
     }
 
     pub fn is_day_complete(&self, day: Day) -> bool {
-        self.data
+        self.data.iter().any(|t| t.day == day && t.is_complete())
+    }
+}
+
+impl Timing {
+    /// Whether both parts were solved.
+    pub fn is_complete(&self) -> bool {
+        self.part_1.is_some() && self.part_2.is_some()
+    }
+
+    fn solved_parts(&self) -> usize {
+        usize::from(self.part_1.is_some()) + usize::from(self.part_2.is_some())
+    }
+}
+
+/// Combine a new timing with the timings of the same day in the last `runs - 1` previous runs.
+/// Every step takes the timing and statistics of the run with the median time for it, the faster of the two middle
+/// runs if the number of runs is even.
+fn median_timing(new: &Timing, previous: &[Timings], runs: usize) -> Timing {
+    let window: Vec<&Timing> = std::iter::once(new)
+        .chain(
+            previous
+                .iter()
+                .rev()
+                .filter_map(|timings| timings.data.iter().find(|t| t.day == new.day)),
+        )
+        .take(runs.max(1))
+        .collect();
+
+    let median = |step: fn(&Timing) -> (Option<StepTiming>, Option<Stats>)| {
+        // steps the new run did not solve stay unsolved.
+        step(new).0?;
+
+        let mut values: Vec<(StepTiming, Option<Stats>)> = window
             .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+            .filter_map(|t| {
+                let (timing, stats) = step(t);
+                timing.map(|timing| (timing, stats))
+            })
+            .collect();
+        values.sort_unstable_by(|a, b| a.0.nanos.total_cmp(&b.0.nanos));
+        Some(values[(values.len() - 1) / 2])
+    };
+
+    let parse = median(|t| (t.parse, t.parse_stats));
+    let part_1 = median(|t| (t.part_1, t.part_1_stats));
+    let part_2 = median(|t| (t.part_2, t.part_2_stats));
+
+    Timing {
+        day: new.day,
+        parse: parse.map(|s| s.0),
+        part_1: part_1.map(|s| s.0),
+        part_2: part_2.map(|s| s.0),
+        parse_stats: parse.and_then(|s| s.1),
+        part_1_stats: part_1.and_then(|s| s.1),
+        part_2_stats: part_2.and_then(|s| s.1),
+        parse_status: new.parse_status,
+        part_1_status: new.part_1_status,
+        part_2_status: new.part_2_status,
+        total_nanos: [parse, part_1, part_2]
+            .iter()
+            .flatten()
+            .map(|s| s.0.nanos)
+            .sum(),
     }
 }
 
+fn get_path(year: Year) -> String {
+    format!("{}/{TIMINGS_FILE_NAME}", data_dir(year))
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl From<Timings> for JsonValue {
     fn from(value: Timings) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
+        map.insert(
+            "version".into(),
+            JsonValue::Number(f64::from(TIMINGS_VERSION)),
+        );
+
         map.insert(
             "data".into(),
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
         );
 
+        map.insert(
+            "environment".into(),
+            value
+                .environment
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -88,9 +292,13 @@ impl TryFrom<String> for Timings {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
 
-        let json_data = json
+        let document = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        let version = read_version(document.get("version"))?;
+
+        let json_data = document
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
@@ -99,12 +307,34 @@ impl TryFrom<String> for Timings {
         Ok(Timings {
             data: json_data
                 .iter()
-                .map(Timing::try_from)
+                .map(|timing| Timing::from_json(timing, version))
                 .collect::<Result<_, _>>()?,
+            environment: read_environment(document.get("environment"))?,
         })
     }
 }
 
+/// Read the schema version of a document that contains timings. Documents without a version are version 1.
+pub fn read_version(value: Option<&JsonValue>) -> Result<u32, String> {
+    let Some(value) = value else {
+        return Ok(1);
+    };
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let version = value
+        .get::<f64>()
+        .map(|v| *v as u32)
+        .ok_or("expected `json.version` to be a number.")?;
+
+    if version > TIMINGS_VERSION {
+        return Err(format!(
+            "timings were stored with a newer version ({version}) of the template."
+        ));
+    }
+
+    Ok(version)
+}
+
 /* -------------------------------------------------------------------------- */
 
 impl From<&Timing> for JsonValue {
@@ -114,33 +344,59 @@ impl From<&Timing> for JsonValue {
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
+        for (key, step) in [
+            ("parse", value.parse),
+            ("part_1", value.part_1),
+            ("part_2", value.part_2),
+        ] {
+            map.insert(
+                key.into(),
+                step.as_ref().map_or(JsonValue::Null, JsonValue::from),
+            );
+        }
 
         map.insert(
-            "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            "parse_stats".into(),
+            value
+                .parse_stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
         );
 
         map.insert(
-            "part_2".into(),
-            match part_2 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            "part_1_stats".into(),
+            value
+                .part_1_stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
         );
 
+        map.insert(
+            "part_2_stats".into(),
+            value
+                .part_2_stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        // statuses are only written for steps that did not finish.
+        for (key, status) in [
+            ("parse_status", value.parse_status),
+            ("part_1_status", value.part_1_status),
+            ("part_2_status", value.part_2_status),
+        ] {
+            if let Some(status) = status {
+                map.insert(key.into(), JsonValue::String(status.as_str().into()));
+            }
+        }
+
         JsonValue::Object(map)
     }
 }
 
-impl TryFrom<&JsonValue> for Timing {
-    type Error = String;
-
-    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+impl Timing {
+    /// Read a timing stored with schema `version`, see [`TIMINGS_VERSION`].
+    pub fn from_json(value: &JsonValue, version: u32) -> Result<Self, String> {
         let json = value
             .get::<HashMap<String, JsonValue>>()
             .ok_or("Expected timing to be a JSON object.")?;
@@ -151,65 +407,195 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
-
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
-
         let total_nanos = json
             .get("total_nanos")
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: timings stored before parse steps and statistics were introduced do not have these keys.
+        let parse_stats = read_stats(json.get("parse_stats"))?;
+        let part_1_stats = read_stats(json.get("part_1_stats"))?;
+        let part_2_stats = read_stats(json.get("part_2_stats"))?;
+
+        let read_step = |key: &str, stats: Option<Stats>| match json.get(key) {
+            Some(v) if !v.is_null() => match version {
+                1 => migrate_step(v, stats)
+                    .map(Some)
+                    .ok_or(format!("Expected timing.{key} to be null or a duration.")),
+                _ => StepTiming::try_from(v).map(Some),
+            },
+            Some(_) => Ok(None),
+            // only the parse step is optional.
+            None if key == "parse" => Ok(None),
+            None => Err(format!("Expected timing to have key `{key}`.")),
+        };
+
+        let parse = read_step("parse", parse_stats)?;
+        let part_1 = read_step("part_1", part_1_stats)?;
+        let part_2 = read_step("part_2", part_2_stats)?;
+
+        let parse_status = read_status(json.get("parse_status"))?;
+        let part_1_status = read_status(json.get("part_1_status"))?;
+        let part_2_status = read_status(json.get("part_2_status"))?;
+
         Ok(Timing {
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
+            parse,
+            part_1,
+            part_2,
+            parse_stats,
+            part_1_stats,
+            part_2_stats,
+            parse_status,
+            part_1_status,
+            part_2_status,
             total_nanos,
         })
     }
 }
 
+/// Convert a version 1 step, a duration formatted like `"74.1ns"`, to a [`StepTiming`].
+/// The sample count was not stored in version 1, it is taken from the statistics if present.
+fn migrate_step(value: &JsonValue, stats: Option<Stats>) -> Option<StepTiming> {
+    Some(StepTiming {
+        nanos: parse_nanos(value.get::<String>()?)?,
+        samples: stats.map_or(1, |s| s.samples),
+    })
+}
+
+/// Read the optional environment of a document that contains timings.
+pub fn read_environment(value: Option<&JsonValue>) -> Result<Option<Environment>, String> {
+    match value {
+        Some(v) if !v.is_null() => Environment::try_from(v).map(Some),
+        _ => Ok(None),
+    }
+}
+
+fn read_stats(value: Option<&JsonValue>) -> Result<Option<Stats>, String> {
+    match value {
+        Some(v) if !v.is_null() => Stats::try_from(v).map(Some),
+        _ => Ok(None),
+    }
+}
+
+fn read_status(value: Option<&JsonValue>) -> Result<Option<PartStatus>, String> {
+    match value {
+        Some(v) if !v.is_null() => v
+            .get::<String>()
+            .and_then(|s| PartStatus::parse(s))
+            .map(Some)
+            .ok_or_else(|| "Expected timing status to be a part status.".into()),
+        _ => Ok(None),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&StepTiming> for JsonValue {
+    fn from(value: &StepTiming) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("nanos".into(), JsonValue::Number(value.nanos));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for StepTiming {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected step timing to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected step timing.{key} to be a number."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(StepTiming {
+            nanos: number("nanos")?,
+            samples: number("samples")? as u128,
+        })
+    }
+}
+
 /* -------------------------------------------------------------------------- */
 
 #[cfg(feature = "test_lib")]
 mod tests {
     use crate::day;
 
-    use super::{Timing, Timings};
+    use super::{StepTiming, Timing, Timings};
+
+    fn step(nanos: f64) -> StepTiming {
+        StepTiming { nanos, samples: 1 }
+    }
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    parse: None,
+                    part_1: Some(step(10e6)),
+                    part_2: Some(step(20e6)),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_status: None,
+                    part_1_status: None,
+                    part_2_status: None,
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    parse: None,
+                    part_1: Some(step(30e6)),
+                    part_2: Some(step(40e6)),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_status: None,
+                    part_1_status: None,
+                    part_2_status: None,
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
+                    parse: None,
+                    part_1: Some(step(40e6)),
                     part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_status: None,
+                    part_1_status: None,
+                    part_2_status: None,
                     total_nanos: 4e+10,
                 },
             ],
+            environment: None,
         }
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use tinyjson::JsonValue;
+
+        use crate::{
+            day,
+            template::{
+                runner::PartStatus,
+                timings::{StepTiming, Timings},
+            },
+        };
+
+        use super::get_mock_timings;
 
         #[test]
         fn handles_json_timings() {
@@ -218,11 +604,79 @@ mod tests {
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            assert_eq!(timing.part_1.unwrap().nanos, 1_000_000_f64);
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_versioned_json_timings() {
+            let json = r#"{ "version": 2, "data": [{ "day": "01", "parse": null, "part_1": { "nanos": 74.5, "samples": 10000 }, "part_2": null, "total_nanos": 74.5 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(
+                timing.part_1,
+                Some(StepTiming {
+                    nanos: 74.5,
+                    samples: 10000
+                })
+            );
+            assert_eq!(timing.parse, None);
+        }
+
+        #[test]
+        fn migrates_formatted_durations() {
+            let json = r#"{ "data": [{ "day": "01", "parse": "1.5µs", "part_1": "74.13ns", "part_2": "2.0s", "total_nanos": 0, "part_1_stats": { "mean": 74, "median": 74, "min": 70, "max": 80, "stddev": 1, "p95": 78, "samples": 500, "outliers": 0 } }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.parse.unwrap().nanos, 1_500_f64);
+            assert_eq!(timing.part_1.unwrap().nanos, 74.13);
+            assert_eq!(timing.part_1.unwrap().samples, 500);
+            assert_eq!(timing.part_2.unwrap().nanos, 2e9);
+            assert_eq!(timing.part_2.unwrap().samples, 1);
+        }
+
+        #[test]
+        fn roundtrips_timings() {
+            let timings = get_mock_timings();
+            let json = JsonValue::from(timings.clone()).stringify().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data.len(), timings.data.len());
+            assert_eq!(parsed.data[0].part_1, timings.data[0].part_1);
+            assert_eq!(parsed.data[2].part_2, None);
+        }
+
+        #[test]
+        #[should_panic]
+        fn panics_for_newer_versions() {
+            let json = r#"{ "version": 99, "data": [] }"#.to_string();
+            Timings::try_from(json).unwrap();
+        }
+
+        #[test]
+        fn handles_json_timings_with_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_stats": { "mean": 1000000, "median": 1000000, "min": 900000, "max": 1100000, "stddev": 100, "p95": 1050000, "samples": 10, "outliers": 1 }, "part_2_stats": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1_stats.unwrap();
+            assert_eq!(stats.median, 1_000_000_f64);
+            assert_eq!(stats.p95, 1_050_000_f64);
+            assert_eq!(stats.samples, 10);
+            assert_eq!(stats.outliers, 1);
+            assert_eq!(timing.part_2_stats, None);
+        }
+
+        #[test]
+        fn handles_json_timings_with_status() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_2_status": "timed_out" }, { "day": "02", "parse": null, "part_1": null, "part_2": null, "total_nanos": 0, "parse_status": "out_of_memory" }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.parse_status, None);
+            assert_eq!(timing.part_1_status, None);
+            assert_eq!(timing.part_2_status, Some(PartStatus::TimedOut));
+            assert_eq!(timings.data[1].parse_status, Some(PartStatus::OutOfMemory));
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -274,18 +728,28 @@ mod tests {
             template::timings::{Timing, Timings},
         };
 
+        use super::step;
+
         #[test]
         fn handles_completed_days() {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
+                    parse: None,
+                    part_1: Some(step(1e6)),
+                    part_2: Some(step(2e6)),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_status: None,
+                    part_1_status: None,
+                    part_2_status: None,
                     total_nanos: 3_000_000_000_f64,
                 }],
+                environment: None,
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -293,13 +757,21 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
+                    parse: None,
+                    part_1: Some(step(1e6)),
                     part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_status: None,
+                    part_1_status: None,
+                    part_2_status: None,
                     total_nanos: 1_000_000_000_f64,
                 }],
+                environment: None,
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -307,23 +779,34 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: None,
                     part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_status: None,
+                    part_1_status: None,
+                    part_2_status: None,
                     total_nanos: 0.0,
                 }],
+                environment: None,
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
     mod merge {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::{
+                environment::Environment,
+                timings::{MergePolicy, Timing, Timings},
+            },
         };
 
-        use super::get_mock_timings;
+        use super::{get_mock_timings, step};
 
         #[test]
         fn handles_disjunct_timings() {
@@ -331,10 +814,18 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(3),
+                    parse: None,
                     part_1: None,
                     part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    parse_status: None,
+                    part_1_status: None,
+                    part_2_status: None,
                     total_nanos: 0_f64,
                 }],
+                environment: None,
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
@@ -350,21 +841,111 @@ mod tests {
 
             let other = Timings {
                 data: vec![Timing {
-                    day: day!(2),
-                    part_1: None,
-                    part_2: None,
-                    total_nanos: 0_f64,
+                    part_1: Some(step(1e6)),
+                    part_2: Some(step(2e6)),
+                    total_nanos: 3e6,
+                    ..timings.data[1].clone()
                 }],
+                environment: None,
             };
             let merged = timings.merge(&other);
 
             assert_eq!(merged.data.len(), 3);
             assert_eq!(merged.data[0].day, day!(1));
             assert_eq!(merged.data[1].day, day!(2));
-            assert_eq!(merged.data[1].total_nanos, 0_f64);
+            assert_eq!(merged.data[1].total_nanos, 3e6);
             assert_eq!(merged.data[2].day, day!(4));
         }
 
+        #[test]
+        fn keeps_complete_timings_over_partial_ones() {
+            let timings = get_mock_timings();
+
+            let other = Timings {
+                data: vec![
+                    Timing {
+                        part_2: None,
+                        total_nanos: 0_f64,
+                        ..timings.data[1].clone()
+                    },
+                    Timing {
+                        part_2: Some(step(1e6)),
+                        total_nanos: 2e6,
+                        ..timings.data[2].clone()
+                    },
+                ],
+                environment: None,
+            };
+            let merged = timings.merge(&other);
+
+            assert_eq!(merged.data[1].total_nanos, 7e+10);
+            assert_eq!(merged.data[2].total_nanos, 2e6);
+        }
+
+        #[test]
+        fn keeps_best_timings() {
+            let timings = get_mock_timings();
+
+            let faster = Timing {
+                total_nanos: 1e6,
+                ..timings.data[0].clone()
+            };
+            let slower = Timing {
+                total_nanos: 1e+11,
+                ..timings.data[1].clone()
+            };
+            let other = Timings {
+                data: vec![faster, slower],
+                environment: None,
+            };
+            let merged = timings.merge_with(&other, MergePolicy::Best, &[]);
+
+            assert_eq!(merged.data[0].total_nanos, 1e6);
+            assert_eq!(merged.data[1].total_nanos, 7e+10);
+        }
+
+        #[test]
+        fn stores_rolling_medians() {
+            let run = |part_1: f64, part_2: f64| Timings {
+                data: vec![Timing {
+                    part_1: Some(step(part_1)),
+                    part_2: Some(step(part_2)),
+                    total_nanos: part_1 + part_2,
+                    ..get_mock_timings().data[0].clone()
+                }],
+                environment: None,
+            };
+
+            let previous = vec![run(1.0, 1.0), run(100.0, 30.0), run(10.0, 10.0)];
+            let merged =
+                Timings::default().merge_with(&run(50.0, 20.0), MergePolicy::Median(3), &previous);
+
+            let timing = &merged.data[0];
+            assert_eq!(timing.part_1.unwrap().nanos, 50.0);
+            assert_eq!(timing.part_2.unwrap().nanos, 20.0);
+            assert_eq!(timing.total_nanos, 70.0);
+
+            let merged =
+                Timings::default().merge_with(&run(50.0, 20.0), MergePolicy::Median(1), &previous);
+            assert_eq!(merged.data[0].part_1.unwrap().nanos, 50.0);
+
+            let merged =
+                Timings::default().merge_with(&run(5.0, 5.0), MergePolicy::Median(5), &previous);
+            // of an even number of runs, the faster of the two middle runs is taken.
+            assert_eq!(merged.data[0].part_1.unwrap().nanos, 5.0);
+            assert_eq!(merged.data[0].part_2.unwrap().nanos, 5.0);
+        }
+
+        #[test]
+        fn parses_merge_policies() {
+            assert_eq!("newest".parse(), Ok(MergePolicy::Newest));
+            assert_eq!("best".parse(), Ok(MergePolicy::Best));
+            assert_eq!("median".parse(), Ok(MergePolicy::Median(5)));
+            assert_eq!("median:3".parse(), Ok(MergePolicy::Median(3)));
+            assert!("median:0".parse::<MergePolicy>().is_err());
+            assert!("oldest".parse::<MergePolicy>().is_err());
+        }
+
         #[test]
         fn handles_empty_timings() {
             let timings = Timings::default();
@@ -373,6 +954,34 @@ mod tests {
             assert_eq!(merged.data.len(), 3);
         }
 
+        #[test]
+        fn keeps_the_newest_environment() {
+            let environment = Environment {
+                cpu: None,
+                cores: 4,
+                os: "linux x86_64".into(),
+                rustc: "rustc 1.85.0".into(),
+                profile: "release".into(),
+                features: vec![],
+            };
+            let timings = Timings {
+                environment: Some(environment.clone()),
+                ..get_mock_timings()
+            };
+            let other = Timings {
+                data: vec![timings.data[0].clone()],
+                environment: Some(Environment {
+                    cores: 8,
+                    ..environment
+                }),
+            };
+
+            assert_eq!(timings.environment_differences(&other), vec!["cores"]);
+            let merged = timings.merge(&other);
+            assert_eq!(merged.environment.unwrap().cores, 8);
+            assert_eq!(Timings::default().environment_differences(&other).len(), 0);
+        }
+
         #[test]
         fn handles_empty_other_timings() {
             let timings = get_mock_timings();
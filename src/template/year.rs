@@ -0,0 +1,229 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::template::{AllDays, Day};
+
+/// The first year of advent of code.
+const FIRST_YEAR: u16 = 2015;
+
+/// The first year of advent of code with 12 instead of 25 puzzles.
+const FIRST_SHORT_YEAR: u16 = 2025;
+
+/// A valid year of advent of code (i.e. an integer starting at 2015).
+///
+/// # Display
+/// This value displays as a four digit number.
+///
+/// ```
+/// # use advent_of_code::template::Year;
+/// let year = Year::new(2020).unwrap();
+/// assert_eq!(year.to_string(), "2020")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's in the valid range,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if year < FIRST_YEAR {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    /// Reads the configured year from the `AOC_YEAR` environment variable.
+    pub fn from_env() -> Option<Self> {
+        std::env::var("AOC_YEAR").ok()?.parse().ok()
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    /// Reads the year from the path of a solution file named `<year>_<day>.rs`.
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __from_bin_path(path: &str) -> Self {
+        const MESSAGE: &str = "solution files must be named `<year>_<day>.rs`, e.g. `2020_01.rs`";
+
+        // the file name `2020_01.rs` has a fixed length.
+        let bytes = path.as_bytes();
+        assert!(bytes.len() >= 10, "{}", MESSAGE);
+        let start = bytes.len() - 10;
+
+        let mut year = 0;
+        let mut i = 0;
+        while i < 4 {
+            let b = bytes[start + i];
+            assert!(b.is_ascii_digit(), "{}", MESSAGE);
+            year = year * 10 + (b - b'0') as u16;
+            i += 1;
+        }
+
+        assert!(bytes[start + 4] == b'_', "{}", MESSAGE);
+        assert!(year >= FIRST_YEAR, "{}", MESSAGE);
+        Self(year)
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+
+    /// The number of puzzles of the year, 25 up to 2024 and 12 since.
+    ///
+    /// ```
+    /// # use advent_of_code::template::Year;
+    /// assert_eq!(Year::new(2020).unwrap().day_count(), 25);
+    /// assert_eq!(Year::new(2025).unwrap().day_count(), 12);
+    /// ```
+    pub const fn day_count(self) -> u8 {
+        if self.0 >= FIRST_SHORT_YEAR {
+            12
+        } else {
+            25
+        }
+    }
+
+    /// Whether the year has a puzzle for the given day.
+    pub const fn contains(self, day: Day) -> bool {
+        day.into_inner() <= self.day_count()
+    }
+
+    /// An iterator that yields every puzzle day of the year.
+    pub fn days(self) -> AllDays {
+        AllDays::until(self.day_count())
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expecting a year starting at {FIRST_YEAR}")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Identifies a single puzzle by its year and day.
+///
+/// # Display
+/// This value displays as `<year>/<day>`.
+///
+/// ```
+/// # use advent_of_code::template::{Day, PuzzleId, Year};
+/// let puzzle = PuzzleId::new(Year::new(2020).unwrap(), Day::new(8).unwrap());
+/// assert_eq!(puzzle.to_string(), "2020/08");
+/// assert_eq!(puzzle.bin_name(), "2020_08");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl PuzzleId {
+    pub const fn new(year: Year, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    /// Name of the solution binary of the puzzle, e.g. `2020_08`.
+    pub fn bin_name(self) -> String {
+        format!("{}_{}", self.year, self.day)
+    }
+
+    /// Path of a data file of the puzzle, e.g. `data/2020/inputs/08.txt`.
+    pub fn data_path(self, folder: &str, extension: &str) -> String {
+        format!("{}/{folder}/{}.{extension}", data_dir(self.year), self.day)
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.year, self.day)
+    }
+}
+
+/// Directory that holds the data of a year, e.g. `data/2020`.
+pub fn data_dir(year: Year) -> String {
+    format!("data/{year}")
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {{
+        const _ASSERT: () = assert!(
+            $year >= 2015,
+            concat!(
+                "invalid year `",
+                $year,
+                "`, expecting a value starting at 2015"
+            ),
+        );
+        $crate::template::Year::__new_unchecked($year)
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Year;
+
+    #[test]
+    fn reads_year_from_bin_path() {
+        assert_eq!(Year::__from_bin_path("src/bin/2020_01.rs"), Year(2020));
+        assert_eq!(
+            Year::__from_bin_path("/home/user/aoc/src/bin/2023_25.rs"),
+            Year(2023)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_invalid_bin_path() {
+        Year::__from_bin_path("src/bin/01.rs");
+    }
+
+    #[test]
+    fn limits_days_of_short_years() {
+        assert_eq!(Year(2024).days().count(), 25);
+        assert_eq!(Year(2025).days().count(), 12);
+        assert!(Year(2025).contains(crate::day!(12)));
+        assert!(!Year(2025).contains(crate::day!(13)));
+    }
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2020".parse::<Year>().unwrap(), Year(2020));
+        assert!("2014".parse::<Year>().is_err());
+        assert!("20".parse::<Year>().is_err());
+    }
+}